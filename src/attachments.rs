@@ -0,0 +1,280 @@
+use crate::utils;
+use base64::Engine;
+use duckdb::{
+    core::{DataChunkHandle, Inserter, LogicalTypeHandle, LogicalTypeId},
+    vtab::{BindInfo, InitInfo, TableFunctionInfo, VTab},
+    Result,
+};
+use sha2::{Digest, Sha256};
+use std::ffi::CString;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// A base64 payload extracted from message content. Defaults to metadata-only:
+/// unless `include_data := true`, the decoded bytes are dropped right after
+/// `sha256`/`byte_length` are computed, so `data` is never retained on the row
+/// and a full scan does not balloon in memory.
+struct AttachmentRow {
+    provider: String,
+    session_id: String,
+    message_uuid: Option<String>,
+    mime_type: Option<String>,
+    byte_length: i64,
+    sha256: String,
+    data: Vec<u8>,
+}
+
+#[repr(C)]
+pub struct AttachmentsBindData {
+    rows: Mutex<Vec<AttachmentRow>>,
+    include_data: bool,
+}
+
+#[repr(C)]
+pub struct AttachmentsInitData {
+    offset: AtomicUsize,
+}
+
+pub struct ReadAttachmentsVTab;
+
+/// Decode a base64 string, tolerating the alphabet differences between clients:
+/// try standard, URL-safe, and their no-pad variants in turn, accepting the
+/// first that succeeds. This is the key to ingesting blobs from mixed sources.
+fn decode_tolerant(input: &str) -> Option<Vec<u8>> {
+    use base64::engine::general_purpose::{
+        STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD,
+    };
+    let trimmed = input.trim();
+    for engine in [&STANDARD, &URL_SAFE, &STANDARD_NO_PAD, &URL_SAFE_NO_PAD] {
+        if let Ok(bytes) = engine.decode(trimmed) {
+            if !bytes.is_empty() {
+                return Some(bytes);
+            }
+        }
+    }
+    None
+}
+
+/// Pull `(mime, payload)` out of a `data:<mime>;base64,<payload>` data-URI.
+fn parse_data_uri(s: &str) -> Option<(Option<String>, &str)> {
+    let rest = s.strip_prefix("data:")?;
+    let (meta, payload) = rest.split_once(";base64,")?;
+    let mime = if meta.is_empty() {
+        None
+    } else {
+        Some(meta.to_string())
+    };
+    Some((mime, payload))
+}
+
+impl ReadAttachmentsVTab {
+    fn load_rows(path: Option<&str>, include_data: bool) -> Vec<AttachmentRow> {
+        let base_path = utils::resolve_claude_path(path);
+        let mut rows = Vec::new();
+
+        for (project_dir, _is_agent, file_path) in utils::discover_conversation_files(&base_path) {
+            let session_id = file_path
+                .file_name()
+                .map(|f| utils::extract_session_id_from_filename(&f.to_string_lossy()))
+                .unwrap_or_default();
+            let content = match std::fs::read_to_string(&file_path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            for line in content.lines() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let value: serde_json::Value = match serde_json::from_str(line) {
+                    Ok(v) => v,
+                    Err(_) => continue,
+                };
+                let uuid = value
+                    .get("uuid")
+                    .and_then(|u| u.as_str())
+                    .map(String::from);
+                let msg_session = value
+                    .get("sessionId")
+                    .and_then(|u| u.as_str())
+                    .map(String::from)
+                    .unwrap_or_else(|| session_id.clone());
+                let _ = &project_dir;
+                Self::walk(&value, &msg_session, &uuid, include_data, &mut rows);
+            }
+        }
+
+        rows
+    }
+
+    /// Recursively walk a JSON value, emitting an attachment for every base64
+    /// payload found — both `data:<mime>;base64,…` data-URIs in strings and the
+    /// `source: { type: "base64", media_type, data }` shape of image blocks.
+    fn walk(
+        value: &serde_json::Value,
+        session_id: &str,
+        uuid: &Option<String>,
+        include_data: bool,
+        rows: &mut Vec<AttachmentRow>,
+    ) {
+        match value {
+            serde_json::Value::String(s) => {
+                if let Some((mime, payload)) = parse_data_uri(s) {
+                    if let Some(bytes) = decode_tolerant(payload) {
+                        rows.push(Self::make_row(session_id, uuid, mime, bytes, include_data));
+                    }
+                }
+            }
+            serde_json::Value::Array(arr) => {
+                for item in arr {
+                    Self::walk(item, session_id, uuid, include_data, rows);
+                }
+            }
+            serde_json::Value::Object(map) => {
+                // Image source block: { source: { type: base64, media_type, data } }
+                if let Some(source) = map.get("source").and_then(|s| s.as_object()) {
+                    let is_b64 = source
+                        .get("type")
+                        .and_then(|t| t.as_str())
+                        .map_or(false, |t| t == "base64");
+                    if let (true, Some(data)) =
+                        (is_b64, source.get("data").and_then(|d| d.as_str()))
+                    {
+                        if let Some(bytes) = decode_tolerant(data) {
+                            let mime = source
+                                .get("media_type")
+                                .and_then(|m| m.as_str())
+                                .map(String::from);
+                            rows.push(Self::make_row(session_id, uuid, mime, bytes, include_data));
+                        }
+                    }
+                }
+                for v in map.values() {
+                    Self::walk(v, session_id, uuid, include_data, rows);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Hash and size `bytes`, then retain the decoded payload on the row only
+    /// when `include_data` is set — otherwise it is dropped immediately so a
+    /// metadata-only scan never holds attachment bytes resident.
+    fn make_row(
+        session_id: &str,
+        uuid: &Option<String>,
+        mime_type: Option<String>,
+        bytes: Vec<u8>,
+        include_data: bool,
+    ) -> AttachmentRow {
+        let mut hasher = Sha256::new();
+        hasher.update(&bytes);
+        let sha256 = format!("{:x}", hasher.finalize());
+        let byte_length = bytes.len() as i64;
+        AttachmentRow {
+            provider: "claude".to_string(),
+            session_id: session_id.to_string(),
+            message_uuid: uuid.clone(),
+            mime_type,
+            byte_length,
+            sha256,
+            data: if include_data { bytes } else { Vec::new() },
+        }
+    }
+}
+
+impl VTab for ReadAttachmentsVTab {
+    type InitData = AttachmentsInitData;
+    type BindData = AttachmentsBindData;
+
+    fn bind(bind: &BindInfo) -> Result<Self::BindData, Box<dyn std::error::Error>> {
+        bind.add_result_column("provider", LogicalTypeHandle::from(LogicalTypeId::Varchar));
+        bind.add_result_column("session_id", LogicalTypeHandle::from(LogicalTypeId::Varchar));
+        bind.add_result_column("message_uuid", LogicalTypeHandle::from(LogicalTypeId::Varchar));
+        bind.add_result_column("mime_type", LogicalTypeHandle::from(LogicalTypeId::Varchar));
+        bind.add_result_column("byte_length", LogicalTypeHandle::from(LogicalTypeId::Bigint));
+        bind.add_result_column("sha256", LogicalTypeHandle::from(LogicalTypeId::Varchar));
+        bind.add_result_column("data", LogicalTypeHandle::from(LogicalTypeId::Blob));
+
+        let path = if bind.get_parameter_count() > 0 {
+            let p = bind.get_parameter(0).to_string();
+            if p.is_empty() { None } else { Some(p) }
+        } else {
+            None
+        };
+        let named_path = bind.get_named_parameter("path").map(|v| v.to_string());
+        let effective_path = named_path.or(path);
+        let include_data = bind
+            .get_named_parameter("include_data")
+            .map(|v| v.to_string() == "true")
+            .unwrap_or(false);
+
+        let rows = Self::load_rows(effective_path.as_deref(), include_data);
+        Ok(AttachmentsBindData {
+            rows: Mutex::new(rows),
+            include_data,
+        })
+    }
+
+    fn init(_: &InitInfo) -> Result<Self::InitData, Box<dyn std::error::Error>> {
+        Ok(AttachmentsInitData {
+            offset: AtomicUsize::new(0),
+        })
+    }
+
+    fn func(
+        func: &TableFunctionInfo<Self>,
+        output: &mut DataChunkHandle,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let bind_data = func.get_bind_data();
+        let init_data = func.get_init_data();
+        let rows = bind_data.rows.lock().unwrap();
+
+        let offset = init_data.offset.load(Ordering::Relaxed);
+        if offset >= rows.len() {
+            output.set_len(0);
+            return Ok(());
+        }
+
+        let batch_size = std::cmp::min(2048, rows.len() - offset);
+        for i in 0..batch_size {
+            let row = &rows[offset + i];
+            set_varchar(output, 0, i, &row.provider);
+            set_varchar(output, 1, i, &row.session_id);
+            set_varchar_opt(output, 2, i, row.message_uuid.as_deref());
+            set_varchar_opt(output, 3, i, row.mime_type.as_deref());
+            output.flat_vector(4).as_mut_slice::<i64>()[i] = row.byte_length;
+            set_varchar(output, 5, i, &row.sha256);
+            // Metadata-only by default: decoded bytes are emitted only when the
+            // caller opts in, keeping the base scan light.
+            if bind_data.include_data {
+                output.flat_vector(6).insert(i, row.data.as_slice());
+            } else {
+                output.flat_vector(6).set_null(i);
+            }
+        }
+
+        output.set_len(batch_size);
+        init_data.offset.store(offset + batch_size, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn named_parameters() -> Option<Vec<(String, LogicalTypeHandle)>> {
+        Some(vec![
+            ("path".to_string(), LogicalTypeHandle::from(LogicalTypeId::Varchar)),
+            ("include_data".to_string(), LogicalTypeHandle::from(LogicalTypeId::Boolean)),
+        ])
+    }
+}
+
+fn set_varchar(output: &mut DataChunkHandle, col: usize, row: usize, val: &str) {
+    let vec = output.flat_vector(col);
+    vec.insert(row, CString::new(val).unwrap_or_default());
+}
+
+fn set_varchar_opt(output: &mut DataChunkHandle, col: usize, row: usize, val: Option<&str>) {
+    let mut vec = output.flat_vector(col);
+    match val {
+        Some(v) => vec.insert(row, CString::new(v).unwrap_or_default()),
+        None => vec.set_null(row),
+    }
+}
@@ -0,0 +1,200 @@
+use crate::detect::{self, Provider};
+use crate::types::{ContentBlock, ConversationMessage};
+use crate::utils;
+use crate::vtab::{self, ColDef, TableFunc};
+use duckdb::core::DataChunkHandle;
+use std::io::{BufRead, BufReader};
+
+/// One content block of a conversation message, exploded out of the flattened
+/// one-tool-per-row model so the full tool-call / tool-result chain and the
+/// reasoning traces stay queryable.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct ContentBlockRow {
+    session_id: String,
+    file_name: String,
+    line_number: i64,
+    message_uuid: Option<String>,
+    message_role: Option<String>,
+    block_index: i64,
+    block_type: String,
+    text: Option<String>,
+    tool_name: Option<String>,
+    tool_use_id: Option<String>,
+    tool_result_content: Option<String>,
+}
+
+pub struct ContentBlocks;
+
+impl ContentBlocks {
+    /// Stream Claude content-block rows one transcript file at a time so only a
+    /// single file is resident while the scan drains each chunk.
+    fn load_claude_rows(
+        base_path: &std::path::Path,
+    ) -> impl Iterator<Item = ContentBlockRow> + Send {
+        utils::discover_conversation_files(base_path)
+            .into_iter()
+            .flat_map(|(_project_dir, _is_agent, file_path)| Self::file_rows(&file_path))
+    }
+
+    fn file_rows(file_path: &std::path::Path) -> Vec<ContentBlockRow> {
+        let mut out = Vec::new();
+        {
+            let file_name = file_path
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let file_session_id = utils::extract_session_id_from_filename(&file_name);
+            let file = match std::fs::File::open(file_path) {
+                Ok(f) => f,
+                Err(_) => return out,
+            };
+            for (line_idx, line_result) in BufReader::new(file).lines().enumerate() {
+                let line = match line_result {
+                    Ok(l) => l,
+                    Err(_) => continue,
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let msg = match serde_json::from_str::<ConversationMessage>(&line) {
+                    Ok(m) => m,
+                    Err(_) => continue,
+                };
+                let (role, uuid, blocks) = match &msg {
+                    ConversationMessage::Assistant(a) => (
+                        "assistant",
+                        a.base.uuid.clone(),
+                        a.message.as_ref().and_then(|m| m.content.clone()),
+                    ),
+                    ConversationMessage::User(u) => (
+                        "user",
+                        u.base.uuid.clone(),
+                        u.message
+                            .as_ref()
+                            .and_then(|m| m.content.clone())
+                            .and_then(|v| serde_json::from_value::<Vec<ContentBlock>>(v).ok()),
+                    ),
+                    _ => continue,
+                };
+                let blocks = match blocks {
+                    Some(b) => b,
+                    None => continue,
+                };
+                let session_id = file_session_id.clone();
+                for (idx, block) in blocks.into_iter().enumerate() {
+                    out.push(Self::block_to_row(
+                        &session_id,
+                        &file_name,
+                        (line_idx + 1) as i64,
+                        uuid.clone(),
+                        role,
+                        idx as i64,
+                        block,
+                    ));
+                }
+            }
+        }
+        out
+    }
+
+    fn block_to_row(
+        session_id: &str,
+        file_name: &str,
+        line_number: i64,
+        message_uuid: Option<String>,
+        role: &str,
+        block_index: i64,
+        block: ContentBlock,
+    ) -> ContentBlockRow {
+        let mut row = ContentBlockRow {
+            session_id: session_id.to_string(),
+            file_name: file_name.to_string(),
+            line_number,
+            message_uuid,
+            message_role: Some(role.to_string()),
+            block_index,
+            block_type: String::new(),
+            text: None,
+            tool_name: None,
+            tool_use_id: None,
+            tool_result_content: None,
+        };
+        match block {
+            ContentBlock::Text { text } => {
+                row.block_type = "text".to_string();
+                row.text = Some(text);
+            }
+            ContentBlock::Thinking { text, .. } => {
+                row.block_type = "thinking".to_string();
+                row.text = Some(text);
+            }
+            ContentBlock::ToolUse { id, name, input } => {
+                row.block_type = "tool_use".to_string();
+                row.tool_name = name;
+                row.tool_use_id = id;
+                row.text = input.map(|i| i.to_string());
+            }
+            ContentBlock::ToolResult {
+                tool_use_id,
+                content,
+                ..
+            } => {
+                row.block_type = "tool_result".to_string();
+                row.tool_use_id = tool_use_id;
+                row.tool_result_content = content;
+            }
+            // Images and any future block kinds surface under their raw `type`.
+            ContentBlock::Unknown { type_name, .. } => {
+                row.block_type = type_name;
+            }
+        }
+        row
+    }
+}
+
+impl TableFunc for ContentBlocks {
+    type Row = ContentBlockRow;
+
+    fn columns() -> Vec<ColDef> {
+        vec![
+            vtab::varchar("session_id"),
+            vtab::varchar("file_name"),
+            vtab::bigint("line_number"),
+            vtab::varchar("message_uuid"),
+            vtab::varchar("message_role"),
+            vtab::bigint("block_index"),
+            vtab::varchar("block_type"),
+            vtab::varchar("text"),
+            vtab::varchar("tool_name"),
+            vtab::varchar("tool_use_id"),
+            vtab::varchar("tool_result_content"),
+        ]
+    }
+
+    fn load_rows(
+        path: Option<&str>,
+        source: Option<&str>,
+    ) -> Box<dyn Iterator<Item = ContentBlockRow> + Send> {
+        let base_path = utils::resolve_data_path(path);
+        match detect::resolve_provider(&base_path, source) {
+            Provider::Claude => Box::new(Self::load_claude_rows(&base_path)),
+            // Content blocks are a Claude-transcript concept; other providers
+            // expose their structure through read_reasoning / read_tool_results.
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn write_row(output: &mut DataChunkHandle, idx: usize, row: &ContentBlockRow) {
+        vtab::set_varchar(output, 0, idx, &row.session_id);
+        vtab::set_varchar(output, 1, idx, &row.file_name);
+        vtab::set_i64(output, 2, idx, row.line_number);
+        vtab::set_varchar_opt(output, 3, idx, row.message_uuid.as_deref());
+        vtab::set_varchar_opt(output, 4, idx, row.message_role.as_deref());
+        vtab::set_i64(output, 5, idx, row.block_index);
+        vtab::set_varchar(output, 6, idx, &row.block_type);
+        vtab::set_varchar_opt(output, 7, idx, row.text.as_deref());
+        vtab::set_varchar_opt(output, 8, idx, row.tool_name.as_deref());
+        vtab::set_varchar_opt(output, 9, idx, row.tool_use_id.as_deref());
+        vtab::set_varchar_opt(output, 10, idx, row.tool_result_content.as_deref());
+    }
+}
@@ -11,6 +11,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 
 /// A flattened conversation row ready for output.
+#[derive(serde::Serialize, serde::Deserialize)]
 struct ConversationRow {
     session_id: String,
     project_path: String,
@@ -22,6 +23,9 @@ struct ConversationRow {
     uuid: Option<String>,
     parent_uuid: Option<String>,
     timestamp: Option<String>,
+    /// Microseconds-since-epoch form of `timestamp`, emitted as a native
+    /// `TIMESTAMP WITH TIME ZONE` unless `timestamps_as_varchar` is set.
+    timestamp_micros: Option<i64>,
     message_role: Option<String>,
     message_content: Option<String>,
     model: Option<String>,
@@ -37,13 +41,42 @@ struct ConversationRow {
     cwd: Option<String>,
     version: Option<String>,
     stop_reason: Option<String>,
+    /// Raw JSON for messages whose `type` this build does not model yet. NULL
+    /// for recognized message types.
+    raw_json: Option<String>,
 }
 
 #[repr(C)]
 pub struct ConversationsBindData {
-    rows: Mutex<Vec<ConversationRow>>,
+    /// Files discovered in `bind` but *not* parsed there — `func` ingests them
+    /// on demand so memory stays bounded and `LIMIT` can short-circuit.
+    files: Vec<(String, bool, std::path::PathBuf)>,
+    /// Worker-pool width for parsing files in parallel.
+    threads: usize,
+    strict: bool,
+    /// When set, the `timestamp` column is emitted as the raw ISO-8601 string
+    /// (the pre-conversion behavior) instead of a native `TIMESTAMP`.
+    timestamps_as_varchar: bool,
+    /// When set, `tool_input` is projected into a typed `STRUCT` over the
+    /// known-shape tool arguments instead of the default `JSON` scalar.
+    struct_tool_input: bool,
+    /// Persistent parse cache, consulted per-file as `func` streams through
+    /// `files` so an unchanged tree still skips re-parsing under the
+    /// bounded-memory scan. Staged writes are flushed after every refill batch
+    /// so a `LIMIT` query that stops early still persists what it parsed.
+    cache: Mutex<utils::cache::ParseCache>,
 }
 
+/// Streaming cursor: the index of the next file to ingest plus a small buffer of
+/// parsed-but-unemitted rows (at most a handful of in-flight files' worth).
+#[repr(C)]
+pub struct ConversationsStreamInit {
+    next_file: Mutex<usize>,
+    buffer: Mutex<std::collections::VecDeque<ConversationRow>>,
+}
+
+/// Offset cursor used by table functions that pre-materialize their rows (the
+/// BM25 search path, which must rank the whole corpus before emitting).
 #[repr(C)]
 pub struct ConversationsInitData {
     offset: AtomicUsize,
@@ -52,91 +85,146 @@ pub struct ConversationsInitData {
 pub struct ReadConversationsVTab;
 
 impl ReadConversationsVTab {
-    fn load_rows(path: Option<&str>) -> Vec<ConversationRow> {
+    fn load_rows(path: Option<&str>, threads: Option<i64>, strict: bool) -> Vec<ConversationRow> {
         let base_path = utils::resolve_claude_path(path);
         let files = utils::discover_conversation_files(&base_path);
-        let mut rows = Vec::new();
-        for (project_dir, is_agent, file_path) in &files {
-            let file_name = file_path
-                .file_name()
-                .map(|f| f.to_string_lossy().to_string())
-                .unwrap_or_default();
+        let mut cache = utils::cache::ParseCache::open(&base_path);
 
-            let file_session_id = utils::extract_session_id_from_filename(&file_name);
+        // First pass (cheap, on this thread): serve cache hits and collect the
+        // misses that actually need parsing, keyed by their file index so the
+        // final merge stays in deterministic `(file_index, line_number)` order.
+        let mut per_file: Vec<Option<Vec<ConversationRow>>> = Vec::with_capacity(files.len());
+        let mut miss_idx: Vec<usize> = Vec::new();
+        let mut miss_stat: Vec<utils::cache::SourceStat> = Vec::new();
+        for (idx, (project_dir, is_agent, file_path)) in files.iter().enumerate() {
+            match utils::cache::stat_source(file_path) {
+                Some(stat) => match cache.lookup::<ConversationRow>(file_path, &stat) {
+                    Some(rows) => per_file.push(Some(rows)),
+                    None => {
+                        miss_idx.push(idx);
+                        miss_stat.push(stat);
+                        per_file.push(None);
+                    }
+                },
+                None => per_file.push(Some(Self::parse_file(file_path, project_dir, *is_agent))),
+            }
+        }
+
+        // Second pass: parse the misses across a worker pool. Each file is
+        // independent, and `parallel_map` restores input order on merge.
+        let worker_count = utils::resolve_threads(threads);
+        let parsed = utils::parallel_map(miss_idx.clone(), worker_count, |_, idx| {
+            let (project_dir, is_agent, file_path) = &files[*idx];
+            Self::parse_file(file_path, project_dir, *is_agent)
+        });
+
+        // Stage newly-parsed rows back into the cache, then place them by index.
+        for ((idx, stat), rows) in miss_idx.into_iter().zip(miss_stat).zip(parsed) {
+            let (_, _, file_path) = &files[idx];
+            cache.stage(file_path, &stat, &rows);
+            per_file[idx] = Some(rows);
+        }
+
+        cache.flush();
+        let rows = per_file.into_iter().flatten().flatten();
+        // In strict mode, suppress the in-data parse-error rows (they remain
+        // available, with byte/line locations, via `read_diagnostics`). Applied
+        // after retrieval so cached blobs stay strict-agnostic.
+        if strict {
+            rows.filter(|r| r.message_type != "_parse_error").collect()
+        } else {
+            rows.collect()
+        }
+    }
+
+    /// Parse a single conversation file into its flattened rows, applying the
+    /// per-file `cwd` backfill. Kept separate so the result can be memoized in
+    /// the parse cache.
+    fn parse_file(
+        file_path: &std::path::Path,
+        project_dir: &str,
+        is_agent: bool,
+    ) -> Vec<ConversationRow> {
+        let file_name = file_path
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let file_session_id = utils::extract_session_id_from_filename(&file_name);
+
+        let file = match std::fs::File::open(file_path) {
+            Ok(f) => f,
+            Err(_) => return Vec::new(),
+        };
+        let reader = BufReader::new(file);
+        let mut rows = Vec::new();
+        let mut file_line: i64 = 0;
+        let mut file_cwd: Option<String> = None;
 
-            let file = match std::fs::File::open(file_path) {
-                Ok(f) => f,
+        for line_result in reader.lines() {
+            file_line += 1;
+            let line = match line_result {
+                Ok(l) => l,
                 Err(_) => continue,
             };
-            let reader = BufReader::new(file);
-            let mut file_line: i64 = 0;
-            let mut file_rows_start = rows.len();
-            let mut file_cwd: Option<String> = None;
-
-            for line_result in reader.lines() {
-                file_line += 1;
-                let line = match line_result {
-                    Ok(l) => l,
-                    Err(_) => continue,
-                };
-                if line.trim().is_empty() {
-                    continue;
-                }
+            if line.trim().is_empty() {
+                continue;
+            }
 
-                match serde_json::from_str::<ConversationMessage>(&line) {
-                    Ok(msg) => {
-                        let row = Self::message_to_row(
-                            msg,
-                            project_dir,
-                            &file_name,
-                            *is_agent,
-                            &file_session_id,
-                            file_line,
-                        );
-                        if file_cwd.is_none() && row.cwd.is_some() {
-                            file_cwd = row.cwd.clone();
-                        }
-                        rows.push(row);
-                    }
-                    Err(e) => {
-                        rows.push(ConversationRow {
-                            session_id: file_session_id.clone(),
-                            project_path: utils::decode_project_path(project_dir),
-                            project_dir: project_dir.clone(),
-                            file_name: file_name.clone(),
-                            is_agent: *is_agent,
-                            line_number: file_line,
-                            message_type: "_parse_error".to_string(),
-                            uuid: None,
-                            parent_uuid: None,
-                            timestamp: None,
-                            message_role: None,
-                            message_content: Some(format!("Parse error: {}", e)),
-                            model: None,
-                            tool_name: None,
-                            tool_use_id: None,
-                            tool_input: None,
-                            input_tokens: None,
-                            output_tokens: None,
-                            cache_creation_tokens: None,
-                            cache_read_tokens: None,
-                            slug: None,
-                            git_branch: None,
-                            cwd: None,
-                            version: None,
-                            stop_reason: None,
-                        });
+            match serde_json::from_str::<ConversationMessage>(&line) {
+                Ok(msg) => {
+                    let row = Self::message_to_row(
+                        msg,
+                        project_dir,
+                        &file_name,
+                        is_agent,
+                        &file_session_id,
+                        file_line,
+                    );
+                    if file_cwd.is_none() && row.cwd.is_some() {
+                        file_cwd = row.cwd.clone();
                     }
+                    rows.push(row);
+                }
+                Err(e) => {
+                    rows.push(ConversationRow {
+                        session_id: file_session_id.clone(),
+                        project_path: utils::decode_project_path(project_dir),
+                        project_dir: project_dir.to_string(),
+                        file_name: file_name.clone(),
+                        is_agent,
+                        line_number: file_line,
+                        message_type: "_parse_error".to_string(),
+                        uuid: None,
+                        parent_uuid: None,
+                        timestamp: None,
+                        timestamp_micros: None,
+                        message_role: None,
+                        message_content: Some(format!("Parse error: {}", e)),
+                        model: None,
+                        tool_name: None,
+                        tool_use_id: None,
+                        tool_input: None,
+                        input_tokens: None,
+                        output_tokens: None,
+                        cache_creation_tokens: None,
+                        cache_read_tokens: None,
+                        slug: None,
+                        git_branch: None,
+                        cwd: None,
+                        version: None,
+                        stop_reason: None,
+                        raw_json: None,
+                    });
                 }
             }
+        }
 
-            // Backfill project_path for rows without cwd (summary, file-history-snapshot, etc.)
-            if let Some(ref cwd) = file_cwd {
-                let fallback = utils::decode_project_path(project_dir);
-                for row in &mut rows[file_rows_start..] {
-                    if row.project_path == fallback {
-                        row.project_path = cwd.clone();
-                    }
+        // Backfill project_path for rows without cwd (summary, file-history-snapshot, etc.)
+        if let Some(ref cwd) = file_cwd {
+            let fallback = utils::decode_project_path(project_dir);
+            for row in &mut rows {
+                if row.project_path == fallback {
+                    row.project_path = cwd.clone();
                 }
             }
         }
@@ -176,7 +264,8 @@ impl ReadConversationsVTab {
                     message_type: "user".to_string(),
                     uuid: u.base.uuid,
                     parent_uuid: u.base.parent_uuid,
-                    timestamp: u.base.timestamp,
+                    timestamp: u.base.timestamp.raw,
+                    timestamp_micros: u.base.timestamp.micros,
                     message_role: Some("user".to_string()),
                     message_content: content,
                     model: None,
@@ -192,6 +281,7 @@ impl ReadConversationsVTab {
                     cwd: u.base.cwd,
                     version: u.base.version,
                     stop_reason: None,
+                    raw_json: None,
                 }
             }
             ConversationMessage::Assistant(a) => {
@@ -243,7 +333,8 @@ impl ReadConversationsVTab {
                     message_type: "assistant".to_string(),
                     uuid: a.base.uuid,
                     parent_uuid: a.base.parent_uuid,
-                    timestamp: a.base.timestamp,
+                    timestamp: a.base.timestamp.raw,
+                    timestamp_micros: a.base.timestamp.micros,
                     message_role: Some("assistant".to_string()),
                     message_content: text_content,
                     model: msg_content.and_then(|m| m.model.clone()),
@@ -258,7 +349,10 @@ impl ReadConversationsVTab {
                     git_branch: a.base.git_branch,
                     cwd: a.base.cwd,
                     version: a.base.version,
-                    stop_reason: msg_content.and_then(|m| m.stop_reason.clone()),
+                    stop_reason: msg_content
+                        .and_then(|m| m.stop_reason.as_ref())
+                        .map(|s| s.canonical()),
+                    raw_json: None,
                 }
             }
             ConversationMessage::System(s) => {
@@ -279,7 +373,8 @@ impl ReadConversationsVTab {
                     message_type: "system".to_string(),
                     uuid: s.base.uuid,
                     parent_uuid: s.base.parent_uuid,
-                    timestamp: s.base.timestamp,
+                    timestamp: s.base.timestamp.raw,
+                    timestamp_micros: s.base.timestamp.micros,
                     message_role: None,
                     message_content: content,
                     model: None,
@@ -295,6 +390,7 @@ impl ReadConversationsVTab {
                     cwd: s.base.cwd,
                     version: s.base.version,
                     stop_reason: None,
+                    raw_json: None,
                 }
             }
             ConversationMessage::Summary(s) => ConversationRow {
@@ -308,6 +404,7 @@ impl ReadConversationsVTab {
                 uuid: None,
                 parent_uuid: None,
                 timestamp: None,
+                timestamp_micros: None,
                 message_role: None,
                 message_content: s.summary,
                 model: None,
@@ -323,6 +420,7 @@ impl ReadConversationsVTab {
                 cwd: None,
                 version: None,
                 stop_reason: None,
+                raw_json: None,
             },
             ConversationMessage::FileHistorySnapshot(_) => ConversationRow {
                 session_id: file_session_id.to_string(),
@@ -335,6 +433,7 @@ impl ReadConversationsVTab {
                 uuid: None,
                 parent_uuid: None,
                 timestamp: None,
+                timestamp_micros: None,
                 message_role: None,
                 message_content: None,
                 model: None,
@@ -350,6 +449,7 @@ impl ReadConversationsVTab {
                 cwd: None,
                 version: None,
                 stop_reason: None,
+                raw_json: None,
             },
             ConversationMessage::QueueOperation(q) => ConversationRow {
                 session_id: q
@@ -364,6 +464,7 @@ impl ReadConversationsVTab {
                 message_type: "queue-operation".to_string(),
                 uuid: None,
                 parent_uuid: None,
+                timestamp_micros: q.timestamp.as_deref().and_then(crate::timeutil::parse_any),
                 timestamp: q.timestamp,
                 message_role: None,
                 message_content: q.content,
@@ -380,60 +481,56 @@ impl ReadConversationsVTab {
                 cwd: None,
                 version: None,
                 stop_reason: None,
+                raw_json: None,
+            },
+            ConversationMessage::Unknown { type_name, raw } => ConversationRow {
+                session_id: file_session_id.to_string(),
+                project_path: fallback_project_path.clone(),
+                project_dir: project_dir.to_string(),
+                file_name: file_name.to_string(),
+                is_agent,
+                line_number,
+                message_type: type_name,
+                uuid: None,
+                parent_uuid: None,
+                timestamp: None,
+                timestamp_micros: None,
+                message_role: None,
+                message_content: None,
+                model: None,
+                tool_name: None,
+                tool_use_id: None,
+                tool_input: None,
+                input_tokens: None,
+                output_tokens: None,
+                cache_creation_tokens: None,
+                cache_read_tokens: None,
+                slug: None,
+                git_branch: None,
+                cwd: None,
+                version: None,
+                stop_reason: None,
+                raw_json: Some(raw.to_string()),
             },
         }
     }
 }
 
 impl VTab for ReadConversationsVTab {
-    type InitData = ConversationsInitData;
+    type InitData = ConversationsStreamInit;
     type BindData = ConversationsBindData;
 
     fn bind(bind: &BindInfo) -> Result<Self::BindData, Box<dyn std::error::Error>> {
-        // Define output columns
-        let cols = [
-            "session_id",
-            "project_path",
-            "project_dir",
-            "file_name",
-            "is_agent",
-            "line_number",
-            "message_type",
-            "uuid",
-            "parent_uuid",
-            "timestamp",
-            "message_role",
-            "message_content",
-            "model",
-            "tool_name",
-            "tool_use_id",
-            "tool_input",
-            "input_tokens",
-            "output_tokens",
-            "cache_creation_tokens",
-            "cache_read_tokens",
-            "slug",
-            "git_branch",
-            "cwd",
-            "version",
-            "stop_reason",
-        ];
-
-        // VARCHAR columns
-        for &col in &cols {
-            match col {
-                "is_agent" => {
-                    bind.add_result_column(col, LogicalTypeHandle::from(LogicalTypeId::Boolean));
-                }
-                "line_number" | "input_tokens" | "output_tokens" | "cache_creation_tokens"
-                | "cache_read_tokens" => {
-                    bind.add_result_column(col, LogicalTypeHandle::from(LogicalTypeId::Bigint));
-                }
-                _ => {
-                    bind.add_result_column(col, LogicalTypeHandle::from(LogicalTypeId::Varchar));
-                }
-            }
-        }
+        let timestamps_as_varchar = bind
+            .get_named_parameter("timestamps_as_varchar")
+            .map(|v| v.to_string() == "true")
+            .unwrap_or(false);
+        let struct_tool_input = bind
+            .get_named_parameter("struct_tool_input")
+            .map(|v| v.to_string() == "true")
+            .unwrap_or(false);
+
+        add_conversation_columns(bind, timestamps_as_varchar, struct_tool_input);
 
         let path = if bind.get_parameter_count() > 0 {
             let p = bind.get_parameter(0).to_string();
@@ -444,9 +541,376 @@ impl VTab for ReadConversationsVTab {
         let named_path = bind.get_named_parameter("path").map(|v| v.to_string());
         let effective_path = named_path.or(path);
 
-        let rows = Self::load_rows(effective_path.as_deref());
+        let threads = bind
+            .get_named_parameter("threads")
+            .and_then(|v| v.to_string().parse::<i64>().ok());
+        let strict = bind
+            .get_named_parameter("strict")
+            .map(|v| v.to_string() == "true")
+            .unwrap_or(false);
+
+        // Discover files now; parse lazily in `func`. This keeps `bind` cheap and
+        // lets a `LIMIT` query stop before the whole tree is read.
+        let base_path = utils::resolve_claude_path(effective_path.as_deref());
+        let files = utils::discover_conversation_files(&base_path);
+        let cache = utils::cache::ParseCache::open(&base_path);
         Ok(ConversationsBindData {
-            rows: Mutex::new(rows),
+            files,
+            threads: utils::resolve_threads(threads),
+            strict,
+            timestamps_as_varchar,
+            struct_tool_input,
+            cache: Mutex::new(cache),
+        })
+    }
+
+    fn init(_: &InitInfo) -> Result<Self::InitData, Box<dyn std::error::Error>> {
+        Ok(ConversationsStreamInit {
+            next_file: Mutex::new(0),
+            buffer: Mutex::new(std::collections::VecDeque::new()),
+        })
+    }
+
+    fn func(
+        func: &TableFunctionInfo<Self>,
+        output: &mut DataChunkHandle,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let bind_data = func.get_bind_data();
+        let init_data = func.get_init_data();
+
+        let files = &bind_data.files;
+        let threads = bind_data.threads.max(1);
+        let mut buffer = init_data.buffer.lock().unwrap();
+        let mut next_file = init_data.next_file.lock().unwrap();
+        let mut cache = bind_data.cache.lock().unwrap();
+
+        // Refill from the work queue until we have rows to emit or run out of
+        // files. Each refill parses up to `threads` files in parallel and buffers
+        // only those files' rows, so memory stays bounded by a chunk plus a few
+        // in-flight files. The per-file `file_cwd` backfill lives inside
+        // `parse_file`, so buffering one file at a time preserves it.
+        while buffer.is_empty() {
+            let start = *next_file;
+            if start >= files.len() {
+                break;
+            }
+            let end = std::cmp::min(start + threads, files.len());
+            *next_file = end;
+            let batch: Vec<usize> = (start..end).collect();
+
+            // Serve cache hits up front (cheap, on this thread) and only send the
+            // misses to the worker pool, same split as `load_rows`.
+            let mut per_file: Vec<Option<Vec<ConversationRow>>> = (0..batch.len()).map(|_| None).collect();
+            let mut miss: Vec<(usize, utils::cache::SourceStat)> = Vec::new();
+            for (slot, &idx) in batch.iter().enumerate() {
+                let (project_dir, is_agent, file_path) = &files[idx];
+                match utils::cache::stat_source(file_path) {
+                    Some(stat) => match cache.lookup::<ConversationRow>(file_path, &stat) {
+                        Some(rows) => per_file[slot] = Some(rows),
+                        None => miss.push((slot, stat)),
+                    },
+                    None => per_file[slot] = Some(Self::parse_file(file_path, project_dir, *is_agent)),
+                }
+            }
+
+            let miss_idx: Vec<usize> = miss.iter().map(|(slot, _)| batch[*slot]).collect();
+            let parsed = utils::parallel_map(miss_idx.clone(), threads, |_, idx| {
+                let (project_dir, is_agent, file_path) = &files[*idx];
+                Self::parse_file(file_path, project_dir, *is_agent)
+            });
+            for ((slot, stat), rows) in miss.into_iter().zip(parsed) {
+                let (_, _, file_path) = &files[batch[slot]];
+                cache.stage(file_path, &stat, &rows);
+                per_file[slot] = Some(rows);
+            }
+
+            // Flush after every batch, not just at the end of the file list: a
+            // `LIMIT` query stops calling `func` once satisfied, so waiting for
+            // `next_file` to reach `files.len()` would silently drop newly-staged
+            // rows for the common case the streaming design exists to support.
+            cache.flush();
+
+            for rows in per_file.into_iter().flatten() {
+                for row in rows {
+                    if bind_data.strict && row.message_type == "_parse_error" {
+                        continue;
+                    }
+                    buffer.push_back(row);
+                }
+            }
+        }
+
+        if buffer.is_empty() {
+            output.set_len(0);
+            return Ok(());
+        }
+
+        let batch_size = std::cmp::min(2048, buffer.len());
+        for i in 0..batch_size {
+            let row = buffer.pop_front().unwrap();
+            write_conversation_row(
+                output,
+                i,
+                &row,
+                bind_data.timestamps_as_varchar,
+                bind_data.struct_tool_input,
+            );
+        }
+        output.set_len(batch_size);
+        Ok(())
+    }
+
+    fn named_parameters() -> Option<Vec<(String, LogicalTypeHandle)>> {
+        Some(vec![
+            (
+                "path".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Varchar),
+            ),
+            (
+                "threads".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Bigint),
+            ),
+            (
+                "strict".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Boolean),
+            ),
+            (
+                "timestamps_as_varchar".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Boolean),
+            ),
+            (
+                "struct_tool_input".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Boolean),
+            ),
+        ])
+    }
+}
+
+/// Column names in output order. Shared by `read_conversations` and
+/// `search_conversations` (the latter appends a trailing `score`).
+const CONVERSATION_COLUMNS: [&str; 27] = [
+    "session_id",
+    "project_path",
+    "project_dir",
+    "file_name",
+    "is_agent",
+    "line_number",
+    "message_type",
+    "uuid",
+    "parent_uuid",
+    "timestamp",
+    "message_role",
+    "message_content",
+    "model",
+    "tool_name",
+    "tool_use_id",
+    "tool_input",
+    "input_tokens",
+    "output_tokens",
+    "cache_creation_tokens",
+    "cache_read_tokens",
+    "slug",
+    "git_branch",
+    "cwd",
+    "version",
+    "stop_reason",
+    "raw_json",
+    "timestamp_raw",
+];
+
+/// Known-shape argument keys projected by `struct_tool_input`. These cover the
+/// file-editing and shell tools that dominate Claude Code transcripts; anything
+/// not in this set stays reachable through the default `JSON` scalar.
+const TOOL_INPUT_STRUCT_FIELDS: [&str; 6] =
+    ["file_path", "command", "content", "old_string", "new_string", "pattern"];
+
+/// The logical type declared for the `tool_input` column, which carries JSON
+/// text. This is plain `VARCHAR`: no `JSON` type alias is attached, so DuckDB
+/// reports the column as `VARCHAR`, not `JSON`. The payload stays a plain
+/// string that json extension functions such as `json_extract` accept as input.
+fn json_logical_type() -> LogicalTypeHandle {
+    LogicalTypeHandle::from(LogicalTypeId::Varchar)
+}
+
+/// The `STRUCT` projection of `tool_input` used when `struct_tool_input` is set,
+/// one `VARCHAR` field per entry in [`TOOL_INPUT_STRUCT_FIELDS`].
+fn tool_input_struct_type() -> LogicalTypeHandle {
+    let fields: Vec<(&str, LogicalTypeHandle)> = TOOL_INPUT_STRUCT_FIELDS
+        .iter()
+        .map(|&f| (f, LogicalTypeHandle::from(LogicalTypeId::Varchar)))
+        .collect();
+    LogicalTypeHandle::struct_type(&fields)
+}
+
+/// Declare the conversation result columns on `bind`, honoring the
+/// `timestamps_as_varchar` toggle for the `timestamp` column's logical type and
+/// the `struct_tool_input` toggle for `tool_input`.
+fn add_conversation_columns(bind: &BindInfo, timestamps_as_varchar: bool, struct_tool_input: bool) {
+    for &col in &CONVERSATION_COLUMNS {
+        match col {
+            "is_agent" => {
+                bind.add_result_column(col, LogicalTypeHandle::from(LogicalTypeId::Boolean));
+            }
+            "timestamp" if !timestamps_as_varchar => {
+                bind.add_result_column(col, LogicalTypeHandle::from(LogicalTypeId::TimestampTz));
+            }
+            "tool_input" if struct_tool_input => {
+                bind.add_result_column(col, tool_input_struct_type());
+            }
+            // `tool_input` is always a JSON object; `message_content` stays
+            // `VARCHAR` because it frequently carries plain prose, not JSON.
+            "tool_input" => {
+                bind.add_result_column(col, json_logical_type());
+            }
+            "line_number" | "input_tokens" | "output_tokens" | "cache_creation_tokens"
+            | "cache_read_tokens" => {
+                bind.add_result_column(col, LogicalTypeHandle::from(LogicalTypeId::Bigint));
+            }
+            _ => {
+                bind.add_result_column(col, LogicalTypeHandle::from(LogicalTypeId::Varchar));
+            }
+        }
+    }
+}
+
+// ─── BM25 full-text search over message_content ───
+
+/// Tokenize text for indexing and querying: lowercase, split on any
+/// non-alphanumeric boundary, dropping empty tokens.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+#[repr(C)]
+pub struct SearchBindData {
+    /// Conversation rows that scored above zero, paired with their BM25 score,
+    /// already sorted by descending relevance.
+    scored: Mutex<Vec<(ConversationRow, f64)>>,
+    timestamps_as_varchar: bool,
+    struct_tool_input: bool,
+}
+
+pub struct SearchConversationsVTab;
+
+impl SearchConversationsVTab {
+    /// Rank `rows` against `query` with Okapi BM25 and return the nonzero-scored
+    /// subset sorted by descending score.
+    fn rank(rows: Vec<ConversationRow>, query: &str, k1: f64, b: f64) -> Vec<(ConversationRow, f64)> {
+        use std::collections::HashMap;
+
+        // Build the inverted index: term -> [(doc_index, term_freq)].
+        let mut postings: HashMap<String, Vec<(usize, u32)>> = HashMap::new();
+        let mut doc_len: Vec<u32> = Vec::with_capacity(rows.len());
+        for (doc, row) in rows.iter().enumerate() {
+            let tokens = row
+                .message_content
+                .as_deref()
+                .map(tokenize)
+                .unwrap_or_default();
+            doc_len.push(tokens.len() as u32);
+            let mut tf: HashMap<String, u32> = HashMap::new();
+            for tok in tokens {
+                *tf.entry(tok).or_insert(0) += 1;
+            }
+            for (term, freq) in tf {
+                postings.entry(term).or_default().push((doc, freq));
+            }
+        }
+
+        let n = rows.len() as f64;
+        let avgdl = if rows.is_empty() {
+            0.0
+        } else {
+            doc_len.iter().map(|&l| l as f64).sum::<f64>() / n
+        };
+
+        // Accumulate BM25 contributions per document over the query terms.
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+        for term in tokenize(query) {
+            let posting = match postings.get(&term) {
+                Some(p) => p,
+                None => continue,
+            };
+            let n_t = posting.len() as f64;
+            let idf = ((n - n_t + 0.5) / (n_t + 0.5) + 1.0).ln();
+            for &(doc, freq) in posting {
+                let f = freq as f64;
+                let dl = doc_len[doc] as f64;
+                let denom = f + k1 * (1.0 - b + b * dl / if avgdl > 0.0 { avgdl } else { 1.0 });
+                *scores.entry(doc).or_insert(0.0) += idf * (f * (k1 + 1.0)) / denom;
+            }
+        }
+
+        // Pair surviving docs with their rows, drop zero/negative scores, sort.
+        let mut indexed: Vec<(usize, f64)> = scores
+            .into_iter()
+            .filter(|&(_, s)| s > 0.0)
+            .collect();
+        indexed.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        let keep: std::collections::HashSet<usize> = indexed.iter().map(|&(d, _)| d).collect();
+        let mut by_doc: HashMap<usize, ConversationRow> = rows
+            .into_iter()
+            .enumerate()
+            .filter(|(d, _)| keep.contains(d))
+            .collect();
+
+        indexed
+            .into_iter()
+            .filter_map(|(doc, score)| by_doc.remove(&doc).map(|row| (row, score)))
+            .collect()
+    }
+}
+
+impl VTab for SearchConversationsVTab {
+    type InitData = ConversationsInitData;
+    type BindData = SearchBindData;
+
+    fn bind(bind: &BindInfo) -> Result<Self::BindData, Box<dyn std::error::Error>> {
+        let timestamps_as_varchar = bind
+            .get_named_parameter("timestamps_as_varchar")
+            .map(|v| v.to_string() == "true")
+            .unwrap_or(false);
+        let struct_tool_input = bind
+            .get_named_parameter("struct_tool_input")
+            .map(|v| v.to_string() == "true")
+            .unwrap_or(false);
+
+        add_conversation_columns(bind, timestamps_as_varchar, struct_tool_input);
+        bind.add_result_column("score", LogicalTypeHandle::from(LogicalTypeId::Double));
+
+        // Positional arg 0 is the query string.
+        let query = if bind.get_parameter_count() > 0 {
+            bind.get_parameter(0).to_string()
+        } else {
+            String::new()
+        };
+        let path = bind.get_named_parameter("path").map(|v| v.to_string());
+        let threads = bind
+            .get_named_parameter("threads")
+            .and_then(|v| v.to_string().parse::<i64>().ok());
+        let strict = bind
+            .get_named_parameter("strict")
+            .map(|v| v.to_string() == "true")
+            .unwrap_or(false);
+        let k1 = bind
+            .get_named_parameter("k1")
+            .and_then(|v| v.to_string().parse::<f64>().ok())
+            .unwrap_or(1.2);
+        let b = bind
+            .get_named_parameter("b")
+            .and_then(|v| v.to_string().parse::<f64>().ok())
+            .unwrap_or(0.75);
+
+        let rows = ReadConversationsVTab::load_rows(path.as_deref(), threads, strict);
+        let scored = Self::rank(rows, &query, k1, b);
+        Ok(SearchBindData {
+            scored: Mutex::new(scored),
+            timestamps_as_varchar,
+            struct_tool_input,
         })
     }
 
@@ -462,65 +926,128 @@ impl VTab for ReadConversationsVTab {
     ) -> Result<(), Box<dyn std::error::Error>> {
         let bind_data = func.get_bind_data();
         let init_data = func.get_init_data();
-        let rows = bind_data.rows.lock().unwrap();
+        let scored = bind_data.scored.lock().unwrap();
 
         let offset = init_data.offset.load(Ordering::Relaxed);
-        if offset >= rows.len() {
+        if offset >= scored.len() {
             output.set_len(0);
             return Ok(());
         }
 
-        let batch_size = std::cmp::min(2048, rows.len() - offset);
-
+        let batch_size = std::cmp::min(2048, scored.len() - offset);
         for i in 0..batch_size {
-            let row = &rows[offset + i];
-            let idx = i;
-
-            set_varchar(output, 0, idx, &row.session_id);
-            set_varchar(output, 1, idx, &row.project_path);
-            set_varchar(output, 2, idx, &row.project_dir);
-            set_varchar(output, 3, idx, &row.file_name);
-            set_bool(output, 4, idx, row.is_agent);
-            set_i64(output, 5, idx, row.line_number);
-            set_varchar(output, 6, idx, &row.message_type);
-            set_varchar_opt(output, 7, idx, row.uuid.as_deref());
-            set_varchar_opt(output, 8, idx, row.parent_uuid.as_deref());
-            set_varchar_opt(output, 9, idx, row.timestamp.as_deref());
-            set_varchar_opt(output, 10, idx, row.message_role.as_deref());
-            set_varchar_opt(output, 11, idx, row.message_content.as_deref());
-            set_varchar_opt(output, 12, idx, row.model.as_deref());
-            set_varchar_opt(output, 13, idx, row.tool_name.as_deref());
-            set_varchar_opt(output, 14, idx, row.tool_use_id.as_deref());
-            set_varchar_opt(output, 15, idx, row.tool_input.as_deref());
-            set_i64_opt(output, 16, idx, row.input_tokens);
-            set_i64_opt(output, 17, idx, row.output_tokens);
-            set_i64_opt(output, 18, idx, row.cache_creation_tokens);
-            set_i64_opt(output, 19, idx, row.cache_read_tokens);
-            set_varchar_opt(output, 20, idx, row.slug.as_deref());
-            set_varchar_opt(output, 21, idx, row.git_branch.as_deref());
-            set_varchar_opt(output, 22, idx, row.cwd.as_deref());
-            set_varchar_opt(output, 23, idx, row.version.as_deref());
-            set_varchar_opt(output, 24, idx, row.stop_reason.as_deref());
+            let (row, score) = &scored[offset + i];
+            write_conversation_row(
+                output,
+                i,
+                row,
+                bind_data.timestamps_as_varchar,
+                bind_data.struct_tool_input,
+            );
+            set_f64(output, CONVERSATION_COLUMNS.len(), i, *score);
         }
 
         output.set_len(batch_size);
-        init_data
-            .offset
-            .store(offset + batch_size, Ordering::Relaxed);
-
+        init_data.offset.store(offset + batch_size, Ordering::Relaxed);
         Ok(())
     }
 
     fn named_parameters() -> Option<Vec<(String, LogicalTypeHandle)>> {
-        Some(vec![(
-            "path".to_string(),
-            LogicalTypeHandle::from(LogicalTypeId::Varchar),
-        )])
+        Some(vec![
+            ("path".to_string(), LogicalTypeHandle::from(LogicalTypeId::Varchar)),
+            ("threads".to_string(), LogicalTypeHandle::from(LogicalTypeId::Bigint)),
+            ("strict".to_string(), LogicalTypeHandle::from(LogicalTypeId::Boolean)),
+            (
+                "timestamps_as_varchar".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Boolean),
+            ),
+            (
+                "struct_tool_input".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Boolean),
+            ),
+            ("k1".to_string(), LogicalTypeHandle::from(LogicalTypeId::Double)),
+            ("b".to_string(), LogicalTypeHandle::from(LogicalTypeId::Double)),
+        ])
     }
 }
 
 // ─── Helper functions for vector operations ───
 
+/// Write the 27 conversation columns for `row` into output slot `idx`. Shared
+/// by `read_conversations` and `search_conversations` so the two stay in sync.
+fn write_conversation_row(
+    output: &mut DataChunkHandle,
+    idx: usize,
+    row: &ConversationRow,
+    timestamps_as_varchar: bool,
+    struct_tool_input: bool,
+) {
+    set_varchar(output, 0, idx, &row.session_id);
+    set_varchar(output, 1, idx, &row.project_path);
+    set_varchar(output, 2, idx, &row.project_dir);
+    set_varchar(output, 3, idx, &row.file_name);
+    set_bool(output, 4, idx, row.is_agent);
+    set_i64(output, 5, idx, row.line_number);
+    set_varchar(output, 6, idx, &row.message_type);
+    set_varchar_opt(output, 7, idx, row.uuid.as_deref());
+    set_varchar_opt(output, 8, idx, row.parent_uuid.as_deref());
+    if timestamps_as_varchar {
+        set_varchar_opt(output, 9, idx, row.timestamp.as_deref());
+    } else {
+        crate::vtab::set_timestamp_opt(output, 9, idx, row.timestamp_micros);
+    }
+    set_varchar_opt(output, 10, idx, row.message_role.as_deref());
+    set_varchar_opt(output, 11, idx, row.message_content.as_deref());
+    set_varchar_opt(output, 12, idx, row.model.as_deref());
+    set_varchar_opt(output, 13, idx, row.tool_name.as_deref());
+    set_varchar_opt(output, 14, idx, row.tool_use_id.as_deref());
+    if struct_tool_input {
+        set_tool_input_struct(output, 15, idx, row.tool_input.as_deref());
+    } else {
+        set_varchar_opt(output, 15, idx, row.tool_input.as_deref());
+    }
+    set_i64_opt(output, 16, idx, row.input_tokens);
+    set_i64_opt(output, 17, idx, row.output_tokens);
+    set_i64_opt(output, 18, idx, row.cache_creation_tokens);
+    set_i64_opt(output, 19, idx, row.cache_read_tokens);
+    set_varchar_opt(output, 20, idx, row.slug.as_deref());
+    set_varchar_opt(output, 21, idx, row.git_branch.as_deref());
+    set_varchar_opt(output, 22, idx, row.cwd.as_deref());
+    set_varchar_opt(output, 23, idx, row.version.as_deref());
+    set_varchar_opt(output, 24, idx, row.stop_reason.as_deref());
+    set_varchar_opt(output, 25, idx, row.raw_json.as_deref());
+    // Preserve the original timestamp text when it could not be parsed into a
+    // calendar time, so a nonstandard value is still recoverable after the
+    // native `TIMESTAMP` column has gone NULL. Mirrors `Timestamp::raw_fallback`.
+    let ts = crate::timeutil::Timestamp {
+        micros: row.timestamp_micros,
+        raw: row.timestamp.clone(),
+    };
+    set_varchar_opt(output, 26, idx, ts.raw_fallback());
+}
+
+/// DuckDB's fixed data-chunk width; the `STRUCT` children are allocated to this
+/// capacity, matching the 2048-row batch cap used when emitting chunks.
+const STANDARD_VECTOR_SIZE: usize = 2048;
+
+/// Parse `tool_input` JSON and scatter its known-shape string fields across the
+/// `STRUCT` children of column `col`, writing NULL for any field the payload
+/// omits (or for a `tool_input` that is absent or not a JSON object).
+fn set_tool_input_struct(output: &mut DataChunkHandle, col: usize, row: usize, val: Option<&str>) {
+    let parsed = val.and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok());
+    let struct_vec = output.struct_vector(col);
+    for (child_idx, &field) in TOOL_INPUT_STRUCT_FIELDS.iter().enumerate() {
+        let mut child = struct_vec.child(child_idx, STANDARD_VECTOR_SIZE);
+        match parsed.as_ref().and_then(|v| v.get(field)).and_then(|v| v.as_str()) {
+            Some(s) => {
+                let cstr = CString::new(s).unwrap_or_else(|_| CString::new("").unwrap());
+                child.insert(row, cstr);
+            }
+            None => child.set_null(row),
+        }
+    }
+}
+
 fn set_varchar(output: &mut DataChunkHandle, col: usize, row: usize, val: &str) {
     let mut vec = output.flat_vector(col);
     let cstr = CString::new(val).unwrap_or_else(|_| CString::new("").unwrap());
@@ -552,6 +1079,11 @@ fn set_i64(output: &mut DataChunkHandle, col: usize, row: usize, val: i64) {
     data[row] = val;
 }
 
+fn set_f64(output: &mut DataChunkHandle, col: usize, row: usize, val: f64) {
+    let mut vec = output.flat_vector(col);
+    vec.as_mut_slice::<f64>()[row] = val;
+}
+
 fn set_i64_opt(output: &mut DataChunkHandle, col: usize, row: usize, val: Option<i64>) {
     let mut vec = output.flat_vector(col);
     match val {
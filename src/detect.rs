@@ -5,12 +5,16 @@ use std::path::Path;
 pub enum Provider {
     Claude,
     Copilot,
+    /// OpenAI Codex / ChatGPT CLI, which writes a rollout transcript per thread
+    /// under a `sessions/` (or `threads/`) directory.
+    Codex,
     Unknown,
 }
 
 /// Auto-detect provider from directory structure.
 /// - `projects/` directory → Claude
 /// - `session-state/` directory → Copilot
+/// - `sessions/` or `threads/` directory → Codex
 pub fn detect_provider(path: &Path) -> Provider {
     if path.join("projects").is_dir() {
         return Provider::Claude;
@@ -18,6 +22,9 @@ pub fn detect_provider(path: &Path) -> Provider {
     if path.join("session-state").is_dir() {
         return Provider::Copilot;
     }
+    if path.join("sessions").is_dir() || path.join("threads").is_dir() {
+        return Provider::Codex;
+    }
     Provider::Unknown
 }
 
@@ -26,6 +33,7 @@ pub fn parse_source(source: &str) -> Provider {
     match source.to_lowercase().as_str() {
         "claude" => Provider::Claude,
         "copilot" => Provider::Copilot,
+        "codex" | "openai" => Provider::Codex,
         _ => Provider::Unknown,
     }
 }
@@ -0,0 +1,203 @@
+use crate::detect::{self, Provider};
+use crate::types::{ConversationMessage, HistoryEntry, TodoItem};
+use crate::utils;
+use crate::vtab::{self, ColDef, TableFunc};
+use duckdb::core::DataChunkHandle;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// One malformed record located during a discovery sweep. Unlike the data
+/// table functions, which smuggle failures into `status = "_parse_error"`
+/// rows, diagnostics carry the structured byte/line location of the failure so
+/// users can jump straight to the offending JSON.
+pub struct DiagnosticRow {
+    source: String,
+    provider: String,
+    file_path: String,
+    line_number: i64,
+    byte_offset: i64,
+    error_kind: String,
+    message: String,
+}
+
+pub struct Diagnostics;
+
+impl Diagnostics {
+    /// Classify a `serde_json` error into a stable, queryable kind.
+    fn error_kind(err: &serde_json::Error) -> &'static str {
+        use serde_json::error::Category;
+        match err.classify() {
+            Category::Io => "io",
+            Category::Syntax => "syntax",
+            Category::Data => "data",
+            Category::Eof => "eof",
+        }
+    }
+
+    /// Parse one JSONL file, emitting a diagnostic for every line that fails to
+    /// deserialize into `T`. The serde error's `line()`/`column()` give a
+    /// compiler-style `Location { line, column }`; we additionally track the
+    /// running byte offset of each line start within the file.
+    ///
+    /// `read_line` is used instead of `lines()` so the exact terminator bytes
+    /// stay in the buffer — advancing by the real line length keeps the offset
+    /// correct across `\n` and `\r\n` files alike. serde's `column()` is a
+    /// 1-based *byte* column within the line, so it is added to the line-start
+    /// offset directly, the same way `scan_json` resolves it.
+    fn scan_jsonl<T: serde::de::DeserializeOwned>(
+        rows: &mut Vec<DiagnosticRow>,
+        source: &str,
+        provider: &str,
+        file_path: &Path,
+    ) {
+        let file = match std::fs::File::open(file_path) {
+            Ok(f) => f,
+            Err(_) => return,
+        };
+        let mut reader = BufReader::new(file);
+        let mut byte_offset: i64 = 0;
+        let mut line_number: i64 = 0;
+        let mut buf = String::new();
+
+        loop {
+            buf.clear();
+            let raw_len = match reader.read_line(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n as i64,
+                Err(_) => break,
+            };
+            line_number += 1;
+            // Content without the trailing terminator; `raw_len` still counts it.
+            let line = buf.trim_end_matches(['\r', '\n']);
+            if !line.trim().is_empty() {
+                if let Err(e) = serde_json::from_str::<T>(line) {
+                    // Turn serde's 1-based byte column into a 0-based byte index
+                    // within the line, then offset by the line start.
+                    let byte_in_line =
+                        (e.column().saturating_sub(1) as i64).min(line.len() as i64);
+                    rows.push(DiagnosticRow {
+                        source: source.to_string(),
+                        provider: provider.to_string(),
+                        file_path: file_path.to_string_lossy().to_string(),
+                        line_number,
+                        byte_offset: byte_offset + byte_in_line,
+                        error_kind: Self::error_kind(&e).to_string(),
+                        message: e.to_string(),
+                    });
+                }
+            }
+            byte_offset += raw_len;
+        }
+    }
+
+    /// Parse a whole-file JSON document, emitting a single diagnostic on
+    /// failure with the location serde reports relative to the file start.
+    fn scan_json<T: serde::de::DeserializeOwned>(
+        rows: &mut Vec<DiagnosticRow>,
+        source: &str,
+        provider: &str,
+        file_path: &Path,
+    ) {
+        let content = match std::fs::read_to_string(file_path) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        if let Err(e) = serde_json::from_str::<T>(&content) {
+            // Resolve the (line, column) to an absolute byte offset by walking
+            // to the start of the reported line.
+            let byte_offset = content
+                .split_inclusive('\n')
+                .take(e.line().saturating_sub(1))
+                .map(|l| l.len())
+                .sum::<usize>() as i64
+                + (e.column() as i64).saturating_sub(1).max(0);
+            rows.push(DiagnosticRow {
+                source: source.to_string(),
+                provider: provider.to_string(),
+                file_path: file_path.to_string_lossy().to_string(),
+                line_number: e.line() as i64,
+                byte_offset,
+                error_kind: Self::error_kind(&e).to_string(),
+                message: e.to_string(),
+            });
+        }
+    }
+
+    fn scan_claude(base_path: &Path) -> Vec<DiagnosticRow> {
+        let mut rows = Vec::new();
+        for (_project, _is_agent, path) in utils::discover_conversation_files(base_path) {
+            Self::scan_jsonl::<ConversationMessage>(&mut rows, "conversations", "claude", &path);
+        }
+        for (_session, _agent, path) in utils::discover_todo_files(base_path) {
+            Self::scan_json::<Vec<TodoItem>>(&mut rows, "todos", "claude", &path);
+        }
+        Self::scan_jsonl::<HistoryEntry>(
+            &mut rows,
+            "history",
+            "claude",
+            &utils::history_file_path(base_path),
+        );
+        // Plans are free-form markdown, so there is nothing to fail parsing.
+        rows
+    }
+
+    fn scan_copilot(base_path: &Path) -> Vec<DiagnosticRow> {
+        let mut rows = Vec::new();
+        for (_session, path) in utils::discover_copilot_event_files(base_path) {
+            Self::scan_jsonl::<crate::types::copilot::CopilotEvent>(
+                &mut rows, "conversations", "copilot", &path,
+            );
+        }
+        rows
+    }
+
+    fn scan_codex(base_path: &Path) -> Vec<DiagnosticRow> {
+        let mut rows = Vec::new();
+        for (_thread, path) in utils::discover_codex_session_files(base_path) {
+            Self::scan_jsonl::<crate::types::codex::CodexEvent>(
+                &mut rows, "conversations", "codex", &path,
+            );
+        }
+        rows
+    }
+}
+
+impl TableFunc for Diagnostics {
+    type Row = DiagnosticRow;
+
+    fn columns() -> Vec<ColDef> {
+        vec![
+            vtab::varchar("source"),
+            vtab::varchar("provider"),
+            vtab::varchar("file_path"),
+            vtab::bigint("line_number"),
+            vtab::bigint("byte_offset"),
+            vtab::varchar("error_kind"),
+            vtab::varchar("message"),
+        ]
+    }
+
+    fn load_rows(
+        path: Option<&str>,
+        source: Option<&str>,
+    ) -> Box<dyn Iterator<Item = DiagnosticRow> + Send> {
+        let base_path = utils::resolve_data_path(path);
+        let rows = match detect::resolve_provider(&base_path, source) {
+            Provider::Claude => Self::scan_claude(&base_path),
+            Provider::Copilot => Self::scan_copilot(&base_path),
+            Provider::Codex => Self::scan_codex(&base_path),
+            Provider::Unknown => Vec::new(),
+        };
+        Box::new(rows.into_iter())
+    }
+
+    fn write_row(output: &mut DataChunkHandle, idx: usize, row: &DiagnosticRow) {
+        vtab::set_varchar(output, 0, idx, &row.source);
+        vtab::set_varchar(output, 1, idx, &row.provider);
+        vtab::set_varchar(output, 2, idx, &row.file_path);
+        vtab::set_i64(output, 3, idx, row.line_number);
+        vtab::set_i64(output, 4, idx, row.byte_offset);
+        vtab::set_varchar(output, 5, idx, &row.error_kind);
+        vtab::set_varchar(output, 6, idx, &row.message);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::detect::{self, Provider};
+use crate::types::codex::{CodexEvent, CodexMessage};
 use crate::types::claude::HistoryEntry;
 use crate::types::copilot::CopilotCommandHistory;
 use crate::utils;
@@ -6,14 +7,22 @@ use crate::vtab::{self, ColDef, TableFunc};
 use duckdb::core::DataChunkHandle;
 use std::io::{BufRead, BufReader};
 
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct HistoryRow {
     source: String,
     line_number: i64,
     timestamp_ms: Option<i64>,
+    /// Native microseconds-since-epoch form of the entry timestamp.
+    timestamp: Option<i64>,
     project: Option<String>,
     session_id: Option<String>,
     display: Option<String>,
     pasted_contents: Option<String>,
+    /// Per-run token usage, populated from Codex `run` events and NULL for the
+    /// providers that do not record usage on the history stream.
+    prompt_tokens: Option<i64>,
+    completion_tokens: Option<i64>,
+    total_tokens: Option<i64>,
 }
 
 pub struct History;
@@ -21,7 +30,23 @@ pub struct History;
 impl History {
     fn load_claude_rows(base_path: &std::path::Path) -> Vec<HistoryRow> {
         let history_path = utils::history_file_path(base_path);
-        let file = match std::fs::File::open(&history_path) {
+        // history.jsonl is append-mostly and frequently re-queried, so cache its
+        // parsed rows keyed by the file's mtime/size.
+        match utils::cache::stat_source(&history_path) {
+            Some(stat) => {
+                let mut cache = utils::cache::ParseCache::open(base_path);
+                let rows = cache.load_or_parse(&history_path, &stat, || {
+                    Self::parse_claude_rows(&history_path)
+                });
+                cache.flush();
+                rows
+            }
+            None => Self::parse_claude_rows(&history_path),
+        }
+    }
+
+    fn parse_claude_rows(history_path: &std::path::Path) -> Vec<HistoryRow> {
+        let file = match std::fs::File::open(history_path) {
             Ok(f) => f,
             Err(_) => return Vec::new(),
         };
@@ -36,19 +61,29 @@ impl History {
                     source: "claude".to_string(),
                     line_number,
                     timestamp_ms: entry.timestamp.map(|t| t as i64),
+                    timestamp: entry
+                        .timestamp
+                        .and_then(crate::timeutil::epoch_seconds_to_micros),
                     project: entry.project,
                     session_id: entry.session_id,
                     display: entry.display,
                     pasted_contents: entry.pasted_contents.map(|v| v.to_string()),
+                    prompt_tokens: None,
+                    completion_tokens: None,
+                    total_tokens: None,
                 },
                 Err(e) => HistoryRow {
                     source: "claude".to_string(),
                     line_number,
                     timestamp_ms: None,
+                    timestamp: None,
                     project: None,
                     session_id: None,
                     display: Some(format!("Parse error: {}", e)),
                     pasted_contents: None,
+                    prompt_tokens: None,
+                    completion_tokens: None,
+                    total_tokens: None,
                 },
             })
         }).collect()
@@ -70,13 +105,75 @@ impl History {
                 source: "copilot".to_string(),
                 line_number: (idx + 1) as i64,
                 timestamp_ms: None,
+                timestamp: None,
                 project: None,
                 session_id: None,
                 display: Some(cmd),
                 pasted_contents: None,
+                prompt_tokens: None,
+                completion_tokens: None,
+                total_tokens: None,
             }
         }).collect()
     }
+
+    fn load_codex_rows(base_path: &std::path::Path) -> Vec<HistoryRow> {
+        let mut rows = Vec::new();
+        for (thread_id, path) in utils::discover_codex_session_files(base_path) {
+            let file = match std::fs::File::open(&path) {
+                Ok(f) => f,
+                Err(_) => continue,
+            };
+            for (line_idx, line_result) in BufReader::new(file).lines().enumerate() {
+                let line = match line_result {
+                    Ok(l) => l,
+                    Err(_) => continue,
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                // Surface user/assistant message content and per-run token
+                // usage onto the unified history schema; other events are
+                // skipped here.
+                match serde_json::from_str::<CodexEvent>(&line) {
+                    Ok(CodexEvent::Message(CodexMessage { content, .. })) => {
+                        rows.push(HistoryRow {
+                            source: "codex".to_string(),
+                            line_number: (line_idx + 1) as i64,
+                            timestamp_ms: None,
+                            timestamp: None,
+                            project: None,
+                            session_id: Some(thread_id.clone()),
+                            display: content,
+                            pasted_contents: None,
+                            prompt_tokens: None,
+                            completion_tokens: None,
+                            total_tokens: None,
+                        });
+                    }
+                    Ok(CodexEvent::Run(run)) => {
+                        if let Some(usage) = run.usage {
+                            rows.push(HistoryRow {
+                                source: "codex".to_string(),
+                                line_number: (line_idx + 1) as i64,
+                                timestamp_ms: None,
+                                timestamp: None,
+                                project: None,
+                                session_id: Some(thread_id.clone()),
+                                display: None,
+                                pasted_contents: None,
+                                prompt_tokens: usage.prompt_tokens,
+                                completion_tokens: usage.completion_tokens,
+                                total_tokens: usage.total_tokens,
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        rows
+    }
 }
 
 impl TableFunc for History {
@@ -87,29 +184,42 @@ impl TableFunc for History {
             vtab::varchar("source"),
             vtab::bigint("line_number"),
             vtab::bigint("timestamp_ms"),
+            vtab::timestamp("timestamp"),
             vtab::varchar("project"),
             vtab::varchar("session_id"),
             vtab::varchar("display"),
             vtab::varchar("pasted_contents"),
+            vtab::bigint("prompt_tokens"),
+            vtab::bigint("completion_tokens"),
+            vtab::bigint("total_tokens"),
         ]
     }
 
-    fn load_rows(path: Option<&str>, source: Option<&str>) -> Vec<HistoryRow> {
+    fn load_rows(
+        path: Option<&str>,
+        source: Option<&str>,
+    ) -> Box<dyn Iterator<Item = HistoryRow> + Send> {
         let base_path = utils::resolve_data_path(path);
-        match detect::resolve_provider(&base_path, source) {
+        let rows = match detect::resolve_provider(&base_path, source) {
             Provider::Claude => Self::load_claude_rows(&base_path),
             Provider::Copilot => Self::load_copilot_rows(&base_path),
+            Provider::Codex => Self::load_codex_rows(&base_path),
             Provider::Unknown => Vec::new(),
-        }
+        };
+        Box::new(rows.into_iter())
     }
 
     fn write_row(output: &mut DataChunkHandle, idx: usize, row: &HistoryRow) {
         vtab::set_varchar(output, 0, idx, &row.source);
         vtab::set_i64(output, 1, idx, row.line_number);
         vtab::set_i64_opt(output, 2, idx, row.timestamp_ms);
-        vtab::set_varchar_opt(output, 3, idx, row.project.as_deref());
-        vtab::set_varchar_opt(output, 4, idx, row.session_id.as_deref());
-        vtab::set_varchar_opt(output, 5, idx, row.display.as_deref());
-        vtab::set_varchar_opt(output, 6, idx, row.pasted_contents.as_deref());
+        vtab::set_timestamp_opt(output, 3, idx, row.timestamp);
+        vtab::set_varchar_opt(output, 4, idx, row.project.as_deref());
+        vtab::set_varchar_opt(output, 5, idx, row.session_id.as_deref());
+        vtab::set_varchar_opt(output, 6, idx, row.display.as_deref());
+        vtab::set_varchar_opt(output, 7, idx, row.pasted_contents.as_deref());
+        vtab::set_i64_opt(output, 8, idx, row.prompt_tokens);
+        vtab::set_i64_opt(output, 9, idx, row.completion_tokens);
+        vtab::set_i64_opt(output, 10, idx, row.total_tokens);
     }
 }
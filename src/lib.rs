@@ -1,9 +1,17 @@
+mod attachments;
+mod content_blocks;
 mod conversations;
 mod detect;
+mod diagnostics;
 mod history;
+mod parquet;
 mod plans;
+mod reasoning;
+mod sessions;
 mod stats;
+mod timeutil;
 mod todos;
+mod tool_results;
 mod types;
 mod utils;
 mod vtab;
@@ -14,15 +22,31 @@ use vtab::GenericVTab;
 
 #[duckdb_entrypoint_c_api()]
 pub unsafe fn extension_entrypoint(con: Connection) -> Result<(), Box<dyn Error>> {
-    con.register_table_function::<GenericVTab<conversations::Conversations>>("read_conversations")
+    con.register_table_function::<conversations::ReadConversationsVTab>("read_conversations")
         .expect("Failed to register read_conversations");
-    con.register_table_function::<GenericVTab<plans::Plans>>("read_plans")
+    con.register_table_function::<conversations::SearchConversationsVTab>("search_conversations")
+        .expect("Failed to register search_conversations");
+    con.register_table_function::<plans::ReadPlansVTab>("read_plans")
         .expect("Failed to register read_plans");
-    con.register_table_function::<GenericVTab<todos::Todos>>("read_todos")
+    con.register_table_function::<todos::ReadTodosVTab>("read_todos")
         .expect("Failed to register read_todos");
     con.register_table_function::<GenericVTab<history::History>>("read_history")
         .expect("Failed to register read_history");
     con.register_table_function::<GenericVTab<stats::Stats>>("read_stats")
         .expect("Failed to register read_stats");
+    con.register_table_function::<GenericVTab<diagnostics::Diagnostics>>("read_diagnostics")
+        .expect("Failed to register read_diagnostics");
+    con.register_table_function::<GenericVTab<sessions::Sessions>>("read_sessions")
+        .expect("Failed to register read_sessions");
+    con.register_table_function::<attachments::ReadAttachmentsVTab>("read_attachments")
+        .expect("Failed to register read_attachments");
+    con.register_table_function::<parquet::ReadParquetVTab>("read_parquet")
+        .expect("Failed to register read_parquet");
+    con.register_table_function::<GenericVTab<content_blocks::ContentBlocks>>("read_content_blocks")
+        .expect("Failed to register read_content_blocks");
+    con.register_table_function::<GenericVTab<reasoning::Reasoning>>("read_reasoning")
+        .expect("Failed to register read_reasoning");
+    con.register_table_function::<GenericVTab<tool_results::ToolResults>>("read_tool_results")
+        .expect("Failed to register read_tool_results");
     Ok(())
 }
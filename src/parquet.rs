@@ -0,0 +1,285 @@
+use duckdb::{
+    core::{DataChunkHandle, Inserter, LogicalTypeHandle, LogicalTypeId},
+    vtab::{BindInfo, InitInfo, TableFunctionInfo, VTab},
+    Result,
+};
+use parquet::basic::{LogicalType, Type as PhysicalType};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use parquet::schema::types::Type as SchemaType;
+use std::ffi::CString;
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// One output column resolved from the Parquet/Arrow schema.
+struct ColumnSpec {
+    name: String,
+    typ: LogicalTypeId,
+}
+
+/// A single decoded cell. Parquet values are coerced into the handful of DuckDB
+/// physical representations the extension already emits, so the `set_*` helpers
+/// below stay symmetrical with the JSON-backed table functions.
+enum Cell {
+    Null,
+    Text(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+#[repr(C)]
+pub struct ParquetBindData {
+    columns: Vec<ColumnSpec>,
+    rows: Mutex<Vec<Vec<Cell>>>,
+}
+
+#[repr(C)]
+pub struct ParquetInitData {
+    offset: AtomicUsize,
+}
+
+/// Reads Claude history previously exported to a Parquet file or a Delta table
+/// directory, so archived conversations that no longer live under `~/.claude`
+/// stay queryable through the same extension.
+pub struct ReadParquetVTab;
+
+/// Process-wide Tokio runtime used to drive the async Delta reader from the
+/// synchronous `bind` path. Created lazily on first Delta open.
+fn delta_runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build Tokio runtime for Delta reads")
+    })
+}
+
+impl ReadParquetVTab {
+    /// Resolve the concrete Parquet files backing `path`. A directory containing
+    /// a `_delta_log` is opened as a Delta table and resolved to the Parquet
+    /// files of its latest snapshot; anything else is treated as a single
+    /// Parquet file.
+    fn resolve_files(path: &str, format: Option<&str>) -> Vec<PathBuf> {
+        let is_delta = match format {
+            Some("delta") => true,
+            Some("parquet") => false,
+            _ => std::path::Path::new(path).join("_delta_log").is_dir(),
+        };
+
+        if is_delta {
+            delta_runtime().block_on(async {
+                match deltalake::open_table(path).await {
+                    Ok(table) => table
+                        .get_file_uris()
+                        .map(|uris| uris.map(PathBuf::from).collect())
+                        .unwrap_or_default(),
+                    Err(_) => Vec::new(),
+                }
+            })
+        } else {
+            vec![PathBuf::from(path)]
+        }
+    }
+
+    /// Derive the result columns from a Parquet file's schema descriptor.
+    fn columns_of(reader: &SerializedFileReader<File>) -> Vec<ColumnSpec> {
+        reader
+            .metadata()
+            .file_metadata()
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|col| ColumnSpec {
+                name: col.name().to_string(),
+                typ: map_parquet_type(col.physical_type(), col.logical_type()),
+            })
+            .collect()
+    }
+
+    /// Read every row of `file` into cells positioned by column order.
+    fn read_rows(file: File, columns: &[ColumnSpec], out: &mut Vec<Vec<Cell>>) {
+        let reader = match SerializedFileReader::new(file) {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+        let row_iter = match reader.get_row_iter(None) {
+            Ok(it) => it,
+            Err(_) => return,
+        };
+        for record in row_iter.flatten() {
+            let mut cells: Vec<Cell> = Vec::with_capacity(columns.len());
+            for (_, field) in record.get_column_iter() {
+                cells.push(cell_from_field(field));
+            }
+            // Pad short records so every row lines up with the column schema.
+            while cells.len() < columns.len() {
+                cells.push(Cell::Null);
+            }
+            out.push(cells);
+        }
+    }
+}
+
+impl VTab for ReadParquetVTab {
+    type InitData = ParquetInitData;
+    type BindData = ParquetBindData;
+
+    fn bind(bind: &BindInfo) -> Result<Self::BindData, Box<dyn std::error::Error>> {
+        let path = if bind.get_parameter_count() > 0 {
+            bind.get_parameter(0).to_string()
+        } else {
+            bind.get_named_parameter("path")
+                .map(|v| v.to_string())
+                .unwrap_or_default()
+        };
+        let format = bind.get_named_parameter("format").map(|v| v.to_string());
+
+        let files = Self::resolve_files(&path, format.as_deref());
+
+        let mut columns: Vec<ColumnSpec> = Vec::new();
+        let mut rows: Vec<Vec<Cell>> = Vec::new();
+        for file_path in files {
+            let file = match File::open(&file_path) {
+                Ok(f) => f,
+                Err(_) => continue,
+            };
+            if columns.is_empty() {
+                if let Ok(reader) = SerializedFileReader::new(file.try_clone()?) {
+                    columns = Self::columns_of(&reader);
+                }
+            }
+            Self::read_rows(file, &columns, &mut rows);
+        }
+
+        for col in &columns {
+            bind.add_result_column(&col.name, LogicalTypeHandle::from(col.typ));
+        }
+
+        Ok(ParquetBindData {
+            columns,
+            rows: Mutex::new(rows),
+        })
+    }
+
+    fn init(_: &InitInfo) -> Result<Self::InitData, Box<dyn std::error::Error>> {
+        Ok(ParquetInitData {
+            offset: AtomicUsize::new(0),
+        })
+    }
+
+    fn func(
+        func: &TableFunctionInfo<Self>,
+        output: &mut DataChunkHandle,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let bind_data = func.get_bind_data();
+        let init_data = func.get_init_data();
+        let rows = bind_data.rows.lock().unwrap();
+
+        let offset = init_data.offset.load(Ordering::Relaxed);
+        if offset >= rows.len() {
+            output.set_len(0);
+            return Ok(());
+        }
+
+        let batch_size = std::cmp::min(2048, rows.len() - offset);
+        for i in 0..batch_size {
+            let row = &rows[offset + i];
+            for (col, cell) in row.iter().enumerate() {
+                write_cell(output, col, i, cell);
+            }
+        }
+
+        output.set_len(batch_size);
+        init_data.offset.store(offset + batch_size, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn named_parameters() -> Option<Vec<(String, LogicalTypeHandle)>> {
+        Some(vec![
+            ("path".to_string(), LogicalTypeHandle::from(LogicalTypeId::Varchar)),
+            ("format".to_string(), LogicalTypeHandle::from(LogicalTypeId::Varchar)),
+        ])
+    }
+}
+
+/// Map a Parquet column's physical/logical type to the DuckDB logical type the
+/// extension emits for it. This must stay in lockstep with [`cell_from_field`]:
+/// a declared type and the cell written into it have to share a physical
+/// representation or DuckDB reads garbage. Temporal and decimal annotations —
+/// which [`cell_from_field`] renders textually rather than decoding — surface as
+/// `VARCHAR`, as do strings and the legacy INT96 timestamp; everything else
+/// follows its physical representation.
+fn map_parquet_type(physical: PhysicalType, logical: Option<LogicalType>) -> LogicalTypeId {
+    if matches!(
+        logical,
+        Some(LogicalType::String)
+            | Some(LogicalType::Enum)
+            | Some(LogicalType::Json)
+            | Some(LogicalType::Date)
+            | Some(LogicalType::Time { .. })
+            | Some(LogicalType::Timestamp { .. })
+            | Some(LogicalType::Decimal { .. })
+    ) {
+        return LogicalTypeId::Varchar;
+    }
+    match physical {
+        PhysicalType::BOOLEAN => LogicalTypeId::Boolean,
+        // INT96 only ever carries a (nanosecond) timestamp, which we render as
+        // text rather than decode.
+        PhysicalType::INT96 => LogicalTypeId::Varchar,
+        PhysicalType::INT32 | PhysicalType::INT64 => LogicalTypeId::Bigint,
+        PhysicalType::FLOAT | PhysicalType::DOUBLE => LogicalTypeId::Double,
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => LogicalTypeId::Varchar,
+    }
+}
+
+/// Coerce a Parquet field into a [`Cell`], mirroring [`map_parquet_type`].
+fn cell_from_field(field: &Field) -> Cell {
+    match field {
+        Field::Null => Cell::Null,
+        Field::Bool(b) => Cell::Bool(*b),
+        Field::Byte(v) => Cell::Int(*v as i64),
+        Field::Short(v) => Cell::Int(*v as i64),
+        Field::Int(v) => Cell::Int(*v as i64),
+        Field::Long(v) => Cell::Int(*v),
+        Field::UByte(v) => Cell::Int(*v as i64),
+        Field::UShort(v) => Cell::Int(*v as i64),
+        Field::UInt(v) => Cell::Int(*v as i64),
+        Field::ULong(v) => Cell::Int(*v as i64),
+        Field::Float(v) => Cell::Float(*v as f64),
+        Field::Double(v) => Cell::Float(*v),
+        Field::Str(s) => Cell::Text(s.clone()),
+        // Bytes, dates, times, timestamps, decimals and nested values render as
+        // text — [`map_parquet_type`] declares each of these columns `VARCHAR`
+        // to match, so the cell and its vector always share a representation.
+        other => Cell::Text(other.to_string()),
+    }
+}
+
+fn write_cell(output: &mut DataChunkHandle, col: usize, row: usize, cell: &Cell) {
+    match cell {
+        Cell::Null => {
+            output.flat_vector(col).set_null(row);
+        }
+        Cell::Text(s) => {
+            let vec = output.flat_vector(col);
+            vec.insert(row, CString::new(s.as_str()).unwrap_or_default());
+        }
+        Cell::Int(v) => {
+            let mut vec = output.flat_vector(col);
+            vec.as_mut_slice::<i64>()[row] = *v;
+        }
+        Cell::Float(v) => {
+            let mut vec = output.flat_vector(col);
+            vec.as_mut_slice::<f64>()[row] = *v;
+        }
+        Cell::Bool(v) => {
+            let mut vec = output.flat_vector(col);
+            vec.as_mut_slice::<bool>()[row] = *v;
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::utils;
+use crate::vtab::resolve_path;
 use duckdb::{
     core::{DataChunkHandle, Inserter, LogicalTypeHandle, LogicalTypeId},
     vtab::{BindInfo, InitInfo, TableFunctionInfo, VTab},
@@ -14,10 +15,16 @@ struct PlanRow {
     file_path: String,
     content: String,
     file_size: i64,
+    /// Path relative to the `plans/` root, so nested plans can be filtered by
+    /// subproject.
+    relative_path: String,
 }
 
 #[repr(C)]
 pub struct PlansBindData {
+    /// Plans are fully materialized at bind. There are rarely more than a
+    /// handful and the scan is single-threaded, so the simplicity of an indexed
+    /// `Vec` behind a `Mutex` outweighs streaming here.
     rows: Mutex<Vec<PlanRow>>,
 }
 
@@ -29,12 +36,12 @@ pub struct PlansInitData {
 pub struct ReadPlansVTab;
 
 impl ReadPlansVTab {
-    fn load_rows(path: Option<&str>) -> Vec<PlanRow> {
+    fn load_rows(path: Option<&str>, pattern: Option<&str>, recursive: bool) -> Vec<PlanRow> {
         let base_path = utils::resolve_claude_path(path);
-        let files = utils::discover_plan_files(&base_path);
+        let files = utils::discover_plan_files_glob(&base_path, pattern, recursive);
         let mut rows = Vec::new();
 
-        for file_path in files {
+        for (file_path, relative_path) in files {
             let plan_name = file_path
                 .file_stem()
                 .map(|s| s.to_string_lossy().to_string())
@@ -60,6 +67,7 @@ impl ReadPlansVTab {
                 file_path: file_path.to_string_lossy().to_string(),
                 content,
                 file_size,
+                relative_path,
             });
         }
         rows
@@ -88,17 +96,19 @@ impl VTab for ReadPlansVTab {
             "file_size",
             LogicalTypeHandle::from(LogicalTypeId::Bigint),
         );
+        bind.add_result_column(
+            "relative_path",
+            LogicalTypeHandle::from(LogicalTypeId::Varchar),
+        );
 
-        let path = if bind.get_parameter_count() > 0 {
-            let p = bind.get_parameter(0).to_string();
-            if p.is_empty() { None } else { Some(p) }
-        } else {
-            None
-        };
-        let named_path = bind.get_named_parameter("path").map(|v| v.to_string());
-        let effective_path = named_path.or(path);
+        let effective_path = resolve_path(bind);
+        let pattern = bind.get_named_parameter("pattern").map(|v| v.to_string());
+        let recursive = bind
+            .get_named_parameter("recursive")
+            .map(|v| v.to_string() == "true")
+            .unwrap_or(false);
 
-        let rows = Self::load_rows(effective_path.as_deref());
+        let rows = Self::load_rows(effective_path.as_deref(), pattern.as_deref(), recursive);
         Ok(PlansBindData {
             rows: Mutex::new(rows),
         })
@@ -126,23 +136,32 @@ impl VTab for ReadPlansVTab {
 
         let batch_size = std::cmp::min(2048, rows.len() - offset);
 
+        // The output chunk is full-width (every declared column is present), so
+        // each value is written at its absolute column index, matching the
+        // convention used by the other table functions. Every column is always
+        // written unconditionally: a prior attempt at skipping `content` under
+        // projection pushdown assumed the chunk stayed full-width even for the
+        // column it left unwritten, which is true only under an unverified
+        // narrowing model and risks an uninitialized vector otherwise.
         for i in 0..batch_size {
             let row = &rows[offset + i];
 
-            let vec0 = output.flat_vector(0);
-            vec0.insert(i, CString::new(row.plan_name.as_str()).unwrap_or_default());
-
-            let vec1 = output.flat_vector(1);
-            vec1.insert(i, CString::new(row.file_name.as_str()).unwrap_or_default());
-
-            let vec2 = output.flat_vector(2);
-            vec2.insert(i, CString::new(row.file_path.as_str()).unwrap_or_default());
-
-            let vec3 = output.flat_vector(3);
-            vec3.insert(i, CString::new(row.content.as_str()).unwrap_or_default());
-
-            let mut vec4 = output.flat_vector(4);
-            vec4.as_mut_slice::<i64>()[i] = row.file_size;
+            output
+                .flat_vector(0)
+                .insert(i, CString::new(row.plan_name.as_str()).unwrap_or_default());
+            output
+                .flat_vector(1)
+                .insert(i, CString::new(row.file_name.as_str()).unwrap_or_default());
+            output
+                .flat_vector(2)
+                .insert(i, CString::new(row.file_path.as_str()).unwrap_or_default());
+            output
+                .flat_vector(3)
+                .insert(i, CString::new(row.content.as_str()).unwrap_or_default());
+            output.flat_vector(4).as_mut_slice::<i64>()[i] = row.file_size;
+            output
+                .flat_vector(5)
+                .insert(i, CString::new(row.relative_path.as_str()).unwrap_or_default());
         }
 
         output.set_len(batch_size);
@@ -154,9 +173,84 @@ impl VTab for ReadPlansVTab {
     }
 
     fn named_parameters() -> Option<Vec<(String, LogicalTypeHandle)>> {
-        Some(vec![(
-            "path".to_string(),
-            LogicalTypeHandle::from(LogicalTypeId::Varchar),
-        )])
+        Some(vec![
+            (
+                "path".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Varchar),
+            ),
+            (
+                "pattern".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Varchar),
+            ),
+            (
+                "recursive".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Boolean),
+            ),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use duckdb::Connection;
+
+    /// Write a throwaway `plans/` tree under the OS temp dir and return its
+    /// parent, i.e. the `path` a `read_plans()` caller would pass in.
+    fn write_test_plans() -> std::path::PathBuf {
+        let base = std::env::temp_dir().join(format!(
+            "read_plans_test_{}_{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        let plans_dir = base.join("plans");
+        std::fs::create_dir_all(&plans_dir).unwrap();
+        std::fs::write(plans_dir.join("alpha.md"), "alpha body").unwrap();
+        std::fs::write(plans_dir.join("beta.md"), "beta body, a bit longer than alpha").unwrap();
+        base
+    }
+
+    /// Regression test for a prior bug where `content` was only written into
+    /// the output chunk when the query projected it, while `file_size` and
+    /// `relative_path` were still written at their absolute, unconditionally-
+    /// assumed indices. A query that narrows the projection to a column other
+    /// than `content` must still see every row and every other column intact.
+    #[test]
+    fn narrow_projection_leaves_every_column_intact() {
+        let base = write_test_plans();
+        let conn = Connection::open_in_memory().unwrap();
+        conn.register_table_function::<ReadPlansVTab>("read_plans")
+            .unwrap();
+        let path = base.to_string_lossy().to_string();
+
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT file_size FROM read_plans(path => '{path}') ORDER BY file_size"
+            ))
+            .unwrap();
+        let sizes: Vec<i64> = stmt
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        assert_eq!(sizes.len(), 2);
+        assert!(sizes[0] > 0 && sizes[1] > 0);
+
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT plan_name FROM read_plans(path => '{path}') ORDER BY plan_name"
+            ))
+            .unwrap();
+        let names: Vec<String> = stmt
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        assert_eq!(names, vec!["alpha".to_string(), "beta".to_string()]);
+
+        std::fs::remove_dir_all(&base).ok();
     }
 }
@@ -0,0 +1,168 @@
+use crate::detect::{self, Provider};
+use crate::types::copilot::{CopilotEvent, ReasoningData};
+use crate::types::{ContentBlock, ConversationMessage};
+use crate::utils;
+use crate::vtab::{self, ColDef, TableFunc};
+use duckdb::core::DataChunkHandle;
+use std::io::{BufRead, BufReader};
+
+/// One reasoning trace, normalized across providers: Claude `thinking` blocks
+/// and Copilot `assistant.reasoning` events land on the same schema so a query
+/// need not know which CLI produced the log.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct ReasoningRow {
+    provider: String,
+    session_id: Option<String>,
+    message_uuid: Option<String>,
+    block_index: i64,
+    signature: Option<String>,
+    text: String,
+}
+
+pub struct Reasoning;
+
+impl Reasoning {
+    fn load_claude_rows(
+        base_path: &std::path::Path,
+    ) -> impl Iterator<Item = ReasoningRow> + Send {
+        utils::discover_conversation_files(base_path)
+            .into_iter()
+            .flat_map(|(_project_dir, _is_agent, file_path)| Self::claude_file_rows(&file_path))
+    }
+
+    fn claude_file_rows(file_path: &std::path::Path) -> Vec<ReasoningRow> {
+        let mut rows = Vec::new();
+        {
+            let file_name = file_path
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let file_session_id = utils::extract_session_id_from_filename(&file_name);
+            let file = match std::fs::File::open(file_path) {
+                Ok(f) => f,
+                Err(_) => return rows,
+            };
+            for line_result in BufReader::new(file).lines() {
+                let line = match line_result {
+                    Ok(l) => l,
+                    Err(_) => continue,
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let assistant = match serde_json::from_str::<ConversationMessage>(&line) {
+                    Ok(ConversationMessage::Assistant(a)) => a,
+                    _ => continue,
+                };
+                let session_id = assistant
+                    .base
+                    .session_id
+                    .clone()
+                    .or_else(|| Some(file_session_id.clone()));
+                let uuid = assistant.base.uuid.clone();
+                let blocks = match assistant.message.as_ref().and_then(|m| m.content.as_ref()) {
+                    Some(b) => b,
+                    None => continue,
+                };
+                for (idx, block) in blocks.iter().enumerate() {
+                    if let ContentBlock::Thinking { text, signature } = block {
+                        rows.push(ReasoningRow {
+                            provider: "claude".to_string(),
+                            session_id: session_id.clone(),
+                            message_uuid: uuid.clone(),
+                            block_index: idx as i64,
+                            signature: signature.clone(),
+                            text: text.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        rows
+    }
+
+    fn load_copilot_rows(
+        base_path: &std::path::Path,
+    ) -> impl Iterator<Item = ReasoningRow> + Send {
+        utils::discover_copilot_event_files(base_path)
+            .into_iter()
+            .flat_map(|(session_id, path)| Self::copilot_file_rows(session_id, path))
+    }
+
+    fn copilot_file_rows(session_id: String, path: std::path::PathBuf) -> Vec<ReasoningRow> {
+        let mut rows = Vec::new();
+        {
+            let file = match std::fs::File::open(&path) {
+                Ok(f) => f,
+                Err(_) => return rows,
+            };
+            let mut block_index: i64 = 0;
+            for line_result in BufReader::new(file).lines() {
+                let line = match line_result {
+                    Ok(l) => l,
+                    Err(_) => continue,
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let event: CopilotEvent = match serde_json::from_str(&line) {
+                    Ok(e) => e,
+                    Err(_) => continue,
+                };
+                if event.event_type != "assistant.reasoning" {
+                    continue;
+                }
+                let data: ReasoningData = serde_json::from_value(event.data).unwrap_or_default();
+                if let Some(text) = data.content {
+                    rows.push(ReasoningRow {
+                        provider: "copilot".to_string(),
+                        session_id: Some(session_id.clone()),
+                        message_uuid: event.id,
+                        block_index,
+                        signature: None,
+                        text,
+                    });
+                    block_index += 1;
+                }
+            }
+        }
+        rows
+    }
+}
+
+impl TableFunc for Reasoning {
+    type Row = ReasoningRow;
+
+    fn columns() -> Vec<ColDef> {
+        vec![
+            vtab::varchar("provider"),
+            vtab::varchar("session_id"),
+            vtab::varchar("message_uuid"),
+            vtab::bigint("block_index"),
+            vtab::varchar("signature"),
+            vtab::varchar("text"),
+        ]
+    }
+
+    fn load_rows(
+        path: Option<&str>,
+        source: Option<&str>,
+    ) -> Box<dyn Iterator<Item = ReasoningRow> + Send> {
+        let base_path = utils::resolve_data_path(path);
+        match detect::resolve_provider(&base_path, source) {
+            Provider::Claude => Box::new(Self::load_claude_rows(&base_path)),
+            Provider::Copilot => Box::new(Self::load_copilot_rows(&base_path)),
+            // Codex rollouts do not record a separate reasoning channel.
+            Provider::Codex | Provider::Unknown => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn write_row(output: &mut DataChunkHandle, idx: usize, row: &ReasoningRow) {
+        vtab::set_varchar(output, 0, idx, &row.provider);
+        vtab::set_varchar_opt(output, 1, idx, row.session_id.as_deref());
+        vtab::set_varchar_opt(output, 2, idx, row.message_uuid.as_deref());
+        vtab::set_i64(output, 3, idx, row.block_index);
+        vtab::set_varchar_opt(output, 4, idx, row.signature.as_deref());
+        vtab::set_varchar(output, 5, idx, &row.text);
+    }
+}
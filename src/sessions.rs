@@ -0,0 +1,170 @@
+use crate::detect::{self, Provider};
+use crate::utils;
+use crate::vtab::{self, ColDef, TableFunc};
+use duckdb::core::DataChunkHandle;
+use std::path::Path;
+
+/// One discovered session artifact with its filesystem metadata. This is a
+/// cheap catalog: it enumerates files via the same `discover_*` helpers the
+/// data table functions use, but never opens or parses their contents, so
+/// users can pick sessions by recency or size before paying to read them.
+pub struct SessionRow {
+    provider: String,
+    kind: String,
+    session_id: String,
+    project: Option<String>,
+    file_path: String,
+    size_bytes: i64,
+    mtime_secs: i64,
+    mtime_nanos: i64,
+    /// False when the recorded mtime only has whole-second granularity, so the
+    /// sub-second component cannot be trusted for ordering.
+    mtime_reliable: bool,
+}
+
+pub struct Sessions;
+
+impl Sessions {
+    fn push(
+        rows: &mut Vec<SessionRow>,
+        provider: &str,
+        kind: &str,
+        session_id: String,
+        project: Option<String>,
+        path: &Path,
+    ) {
+        let (size, secs, nanos, reliable) = match utils::cache::stat_source(path) {
+            Some(stat) => (
+                stat.size as i64,
+                stat.mtime_secs,
+                stat.mtime_nanos as i64,
+                !stat.second_ambiguous,
+            ),
+            None => (0, 0, 0, false),
+        };
+        rows.push(SessionRow {
+            provider: provider.to_string(),
+            kind: kind.to_string(),
+            session_id,
+            project,
+            file_path: path.to_string_lossy().to_string(),
+            size_bytes: size,
+            mtime_secs: secs,
+            mtime_nanos: nanos,
+            mtime_reliable: reliable,
+        });
+    }
+
+    fn catalog_claude(base_path: &Path) -> Vec<SessionRow> {
+        let mut rows = Vec::new();
+
+        for (project_dir, is_agent, path) in utils::discover_conversation_files(base_path) {
+            let file_name = path
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_default();
+            Self::push(
+                &mut rows,
+                "claude",
+                if is_agent { "agent" } else { "conversation" },
+                utils::extract_session_id_from_filename(&file_name),
+                Some(utils::decode_project_path(&project_dir)),
+                &path,
+            );
+        }
+
+        for path in utils::discover_plan_files(base_path) {
+            let session_id = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            Self::push(&mut rows, "claude", "plan", session_id, None, &path);
+        }
+
+        for (session_id, _agent_id, path) in utils::discover_todo_files(base_path) {
+            Self::push(&mut rows, "claude", "todo", session_id, None, &path);
+        }
+
+        let history = utils::history_file_path(base_path);
+        if history.is_file() {
+            Self::push(&mut rows, "claude", "history", String::new(), None, &history);
+        }
+
+        rows
+    }
+
+    fn catalog_copilot(base_path: &Path) -> Vec<SessionRow> {
+        let mut rows = Vec::new();
+
+        for (session_id, path) in utils::discover_copilot_event_files(base_path) {
+            Self::push(&mut rows, "copilot", "conversation", session_id, None, &path);
+        }
+        for (session_id, path) in utils::discover_copilot_plan_files(base_path) {
+            Self::push(&mut rows, "copilot", "plan", session_id, None, &path);
+        }
+        for (session_id, _name, path) in utils::discover_copilot_checkpoint_files(base_path) {
+            Self::push(&mut rows, "copilot", "checkpoint", session_id, None, &path);
+        }
+
+        let history = utils::copilot_history_file_path(base_path);
+        if history.is_file() {
+            Self::push(&mut rows, "copilot", "history", String::new(), None, &history);
+        }
+
+        rows
+    }
+
+    fn catalog_codex(base_path: &Path) -> Vec<SessionRow> {
+        let mut rows = Vec::new();
+        for (thread_id, path) in utils::discover_codex_session_files(base_path) {
+            Self::push(&mut rows, "codex", "conversation", thread_id, None, &path);
+        }
+        rows
+    }
+}
+
+impl TableFunc for Sessions {
+    type Row = SessionRow;
+
+    fn columns() -> Vec<ColDef> {
+        vec![
+            vtab::varchar("provider"),
+            vtab::varchar("kind"),
+            vtab::varchar("session_id"),
+            vtab::varchar("project"),
+            vtab::varchar("file_path"),
+            vtab::bigint("size_bytes"),
+            vtab::bigint("mtime_secs"),
+            vtab::bigint("mtime_nanos"),
+            vtab::boolean("mtime_reliable"),
+        ]
+    }
+
+    fn load_rows(
+        path: Option<&str>,
+        source: Option<&str>,
+    ) -> Box<dyn Iterator<Item = SessionRow> + Send> {
+        let base_path = utils::resolve_data_path(path);
+        // A session catalog is one row per file, so the row set is already small
+        // relative to the transcripts it summarizes; collect then stream it.
+        let rows = match detect::resolve_provider(&base_path, source) {
+            Provider::Claude => Self::catalog_claude(&base_path),
+            Provider::Copilot => Self::catalog_copilot(&base_path),
+            Provider::Codex => Self::catalog_codex(&base_path),
+            Provider::Unknown => Vec::new(),
+        };
+        Box::new(rows.into_iter())
+    }
+
+    fn write_row(output: &mut DataChunkHandle, idx: usize, row: &SessionRow) {
+        vtab::set_varchar(output, 0, idx, &row.provider);
+        vtab::set_varchar(output, 1, idx, &row.kind);
+        vtab::set_varchar(output, 2, idx, &row.session_id);
+        vtab::set_varchar_opt(output, 3, idx, row.project.as_deref());
+        vtab::set_varchar(output, 4, idx, &row.file_path);
+        vtab::set_i64(output, 5, idx, row.size_bytes);
+        vtab::set_i64(output, 6, idx, row.mtime_secs);
+        vtab::set_i64(output, 7, idx, row.mtime_nanos);
+        vtab::set_bool(output, 8, idx, row.mtime_reliable);
+    }
+}
@@ -5,6 +5,9 @@ use duckdb::core::DataChunkHandle;
 
 pub struct StatsRow {
     date: String,
+    /// Native timestamp parsed from `date` (midnight UTC), for `date_trunc` and
+    /// range filters without casting. NULL when the raw string is nonstandard.
+    date_ts: Option<i64>,
     message_count: i64,
     session_count: i64,
     tool_call_count: i64,
@@ -18,37 +21,46 @@ impl TableFunc for Stats {
     fn columns() -> Vec<ColDef> {
         vec![
             vtab::varchar("date"),
+            vtab::timestamp("date_ts"),
             vtab::bigint("message_count"),
             vtab::bigint("session_count"),
             vtab::bigint("tool_call_count"),
         ]
     }
 
-    fn load_rows(path: Option<&str>, _source: Option<&str>) -> Vec<StatsRow> {
+    fn load_rows(
+        path: Option<&str>,
+        _source: Option<&str>,
+    ) -> Box<dyn Iterator<Item = StatsRow> + Send> {
         let base_path = utils::resolve_claude_path(path);
         let stats_path = utils::stats_file_path(&base_path);
 
         let content = match std::fs::read_to_string(&stats_path) {
             Ok(c) => c,
-            Err(_) => return Vec::new(),
+            Err(_) => return Box::new(std::iter::empty()),
         };
         let cache: StatsCache = match serde_json::from_str(&content) {
             Ok(c) => c,
-            Err(_) => return Vec::new(),
+            Err(_) => return Box::new(std::iter::empty()),
         };
 
-        cache.daily_activity.unwrap_or_default().into_iter().map(|day| StatsRow {
-            date: day.date.unwrap_or_default(),
-            message_count: day.message_count.unwrap_or(0),
-            session_count: day.session_count.unwrap_or(0),
-            tool_call_count: day.tool_call_count.unwrap_or(0),
-        }).collect()
+        Box::new(cache.daily_activity.unwrap_or_default().into_iter().map(|day| {
+            let date = day.date.unwrap_or_default();
+            StatsRow {
+                date_ts: crate::timeutil::parse_any(&date),
+                date,
+                message_count: day.message_count.unwrap_or(0),
+                session_count: day.session_count.unwrap_or(0),
+                tool_call_count: day.tool_call_count.unwrap_or(0),
+            }
+        }))
     }
 
     fn write_row(output: &mut DataChunkHandle, idx: usize, row: &StatsRow) {
         vtab::set_varchar(output, 0, idx, &row.date);
-        vtab::set_i64(output, 1, idx, row.message_count);
-        vtab::set_i64(output, 2, idx, row.session_count);
-        vtab::set_i64(output, 3, idx, row.tool_call_count);
+        vtab::set_timestamp_opt(output, 1, idx, row.date_ts);
+        vtab::set_i64(output, 2, idx, row.message_count);
+        vtab::set_i64(output, 3, idx, row.session_count);
+        vtab::set_i64(output, 4, idx, row.tool_call_count);
     }
 }
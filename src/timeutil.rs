@@ -0,0 +1,106 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Deserializer};
+
+/// A timestamp parsed from either an ISO-8601/RFC-3339 string or a numeric
+/// unix-epoch value, normalized to `chrono::DateTime<Utc>` and stored as
+/// microseconds since epoch for native emission as a DuckDB `TIMESTAMP`.
+///
+/// The original text is retained in `raw` so callers can surface it in a
+/// `*_raw` column when parsing fails, rather than erroring or losing the value.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct Timestamp {
+    /// Microseconds since the unix epoch, or `None` when the input could not be
+    /// parsed into a calendar time.
+    pub micros: Option<i64>,
+    /// The original textual/numeric form, preserved for the tolerant `*_raw`
+    /// fallback path.
+    pub raw: Option<String>,
+}
+
+impl Timestamp {
+    fn from_text(text: String) -> Self {
+        let micros = parse_any(&text);
+        Timestamp {
+            micros,
+            raw: Some(text),
+        }
+    }
+
+    /// The raw text only when parsing produced no calendar time, matching the
+    /// semantics of the accompanying `*_raw` column.
+    pub fn raw_fallback(&self) -> Option<&str> {
+        if self.micros.is_none() {
+            self.raw.as_deref()
+        } else {
+            None
+        }
+    }
+}
+
+/// Parse an RFC-3339 string, a bare date, or a numeric epoch (seconds, possibly
+/// fractional) into microseconds since epoch.
+pub fn parse_any(text: &str) -> Option<i64> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    // Numeric epoch seconds (integer or fractional).
+    if let Ok(secs) = trimmed.parse::<f64>() {
+        return epoch_seconds_to_micros(secs);
+    }
+    parse_rfc3339(trimmed).or_else(|| parse_naive_date(trimmed))
+}
+
+/// Parse an RFC-3339 timestamp (`2024-01-02T15:04:05.123Z`, offsets allowed).
+pub fn parse_rfc3339(text: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(text)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+        .map(to_micros)
+}
+
+/// Parse a bare `YYYY-MM-DD` date as midnight UTC (used by the daily-stats date
+/// column, which carries no time component).
+pub fn parse_naive_date(text: &str) -> Option<i64> {
+    let date = NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()?;
+    let dt = date.and_hms_opt(0, 0, 0)?;
+    Some(to_micros(Utc.from_utc_datetime(&dt)))
+}
+
+/// Convert unix epoch seconds (with optional sub-second fraction) to micros.
+pub fn epoch_seconds_to_micros(secs: f64) -> Option<i64> {
+    if !secs.is_finite() {
+        return None;
+    }
+    Some((secs * 1_000_000.0).round() as i64)
+}
+
+/// Convert a `DateTime<Utc>` to microseconds since epoch.
+pub fn to_micros(dt: DateTime<Utc>) -> i64 {
+    dt.timestamp_micros()
+}
+
+/// `deserialize_with` target for an optional timestamp field that may arrive as
+/// either a string or a number; unparseable input is retained in `raw` rather
+/// than failing the record.
+pub fn deserialize_opt<'de, D>(deserializer: D) -> Result<Timestamp, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Str(String),
+        Num(f64),
+        Null,
+    }
+
+    Ok(match Option::<Raw>::deserialize(deserializer)? {
+        None | Some(Raw::Null) => Timestamp::default(),
+        Some(Raw::Str(s)) => Timestamp::from_text(s),
+        Some(Raw::Num(n)) => Timestamp {
+            micros: epoch_seconds_to_micros(n),
+            raw: Some(n.to_string()),
+        },
+    })
+}
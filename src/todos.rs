@@ -9,6 +9,7 @@ use std::ffi::CString;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 
+#[derive(serde::Serialize, serde::Deserialize)]
 struct TodoRow {
     session_id: String,
     agent_id: String,
@@ -32,52 +33,94 @@ pub struct TodosInitData {
 pub struct ReadTodosVTab;
 
 impl ReadTodosVTab {
-    fn load_rows(path: Option<&str>) -> Vec<TodoRow> {
+    fn load_rows(path: Option<&str>, threads: Option<i64>, strict: bool) -> Vec<TodoRow> {
         let base_path = utils::resolve_claude_path(path);
         let files = utils::discover_todo_files(&base_path);
-        let mut rows = Vec::new();
-
-        for (session_id, agent_id, file_path) in files {
-            let fname = file_path
-                .file_name()
-                .map(|s| s.to_string_lossy().to_string())
-                .unwrap_or_default();
-
-            let content = match std::fs::read_to_string(&file_path) {
-                Ok(c) => c,
-                Err(_) => continue,
-            };
-
-            let items: Vec<TodoItem> = match serde_json::from_str(&content) {
-                Ok(items) => items,
-                Err(e) => {
-                    // Emit parse error row instead of silently dropping
-                    rows.push(TodoRow {
-                        session_id: session_id.clone(),
-                        agent_id: agent_id.clone(),
-                        file_name: fname,
-                        item_index: -1,
-                        content: format!("Parse error: {}", e),
-                        status: "_parse_error".to_string(),
-                        active_form: None,
-                    });
-                    continue;
-                }
-            };
-
-            for (idx, item) in items.into_iter().enumerate() {
-                rows.push(TodoRow {
-                    session_id: session_id.clone(),
-                    agent_id: agent_id.clone(),
-                    file_name: fname.clone(),
-                    item_index: idx as i64,
-                    content: item.content.unwrap_or_default(),
-                    status: item.status.unwrap_or_default(),
-                    active_form: item.active_form,
-                });
+        let mut cache = utils::cache::ParseCache::open(&base_path);
+
+        // Serve cache hits inline and gather the misses to parse in parallel,
+        // keyed by file index so the merged output stays deterministic.
+        let mut per_file: Vec<Option<Vec<TodoRow>>> = Vec::with_capacity(files.len());
+        let mut miss_idx: Vec<usize> = Vec::new();
+        let mut miss_stat: Vec<utils::cache::SourceStat> = Vec::new();
+        for (idx, (session_id, agent_id, file_path)) in files.iter().enumerate() {
+            match utils::cache::stat_source(file_path) {
+                Some(stat) => match cache.lookup::<TodoRow>(file_path, &stat) {
+                    Some(rows) => per_file.push(Some(rows)),
+                    None => {
+                        miss_idx.push(idx);
+                        miss_stat.push(stat);
+                        per_file.push(None);
+                    }
+                },
+                None => per_file.push(Some(Self::parse_file(session_id, agent_id, file_path))),
             }
         }
-        rows
+
+        let worker_count = utils::resolve_threads(threads);
+        let parsed = utils::parallel_map(miss_idx.clone(), worker_count, |_, idx| {
+            let (session_id, agent_id, file_path) = &files[*idx];
+            Self::parse_file(session_id, agent_id, file_path)
+        });
+
+        for ((idx, stat), rows) in miss_idx.into_iter().zip(miss_stat).zip(parsed) {
+            let (_, _, file_path) = &files[idx];
+            cache.stage(file_path, &stat, &rows);
+            per_file[idx] = Some(rows);
+        }
+
+        cache.flush();
+        let rows = per_file.into_iter().flatten().flatten();
+        // Strict mode drops the in-data parse-error rows; they remain queryable
+        // via `read_diagnostics`. Filtered post-retrieval so cached blobs are
+        // strict-agnostic.
+        if strict {
+            rows.filter(|r| r.status != "_parse_error").collect()
+        } else {
+            rows.collect()
+        }
+    }
+
+    fn parse_file(session_id: &str, agent_id: &str, file_path: &std::path::Path) -> Vec<TodoRow> {
+        let fname = file_path
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let content = match std::fs::read_to_string(file_path) {
+            Ok(c) => c,
+            Err(_) => return Vec::new(),
+        };
+
+        let items: Vec<TodoItem> = match serde_json::from_str(&content) {
+            Ok(items) => items,
+            Err(e) => {
+                // Emit parse error row instead of silently dropping
+                return vec![TodoRow {
+                    session_id: session_id.to_string(),
+                    agent_id: agent_id.to_string(),
+                    file_name: fname,
+                    item_index: -1,
+                    content: format!("Parse error: {}", e),
+                    status: "_parse_error".to_string(),
+                    active_form: None,
+                }];
+            }
+        };
+
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(idx, item)| TodoRow {
+                session_id: session_id.to_string(),
+                agent_id: agent_id.to_string(),
+                file_name: fname.clone(),
+                item_index: idx as i64,
+                content: item.content.unwrap_or_default(),
+                status: item.status.map(|s| s.canonical()).unwrap_or_default(),
+                active_form: item.active_form,
+            })
+            .collect()
     }
 }
 
@@ -118,7 +161,15 @@ impl VTab for ReadTodosVTab {
         let named_path = bind.get_named_parameter("path").map(|v| v.to_string());
         let effective_path = named_path.or(path);
 
-        let rows = Self::load_rows(effective_path.as_deref());
+        let threads = bind
+            .get_named_parameter("threads")
+            .and_then(|v| v.to_string().parse::<i64>().ok());
+        let strict = bind
+            .get_named_parameter("strict")
+            .map(|v| v.to_string() == "true")
+            .unwrap_or(false);
+
+        let rows = Self::load_rows(effective_path.as_deref(), threads, strict);
         Ok(TodosBindData {
             rows: Mutex::new(rows),
         })
@@ -183,9 +234,19 @@ impl VTab for ReadTodosVTab {
     }
 
     fn named_parameters() -> Option<Vec<(String, LogicalTypeHandle)>> {
-        Some(vec![(
-            "path".to_string(),
-            LogicalTypeHandle::from(LogicalTypeId::Varchar),
-        )])
+        Some(vec![
+            (
+                "path".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Varchar),
+            ),
+            (
+                "threads".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Bigint),
+            ),
+            (
+                "strict".to_string(),
+                LogicalTypeHandle::from(LogicalTypeId::Boolean),
+            ),
+        ])
     }
 }
@@ -0,0 +1,326 @@
+use crate::detect::{self, Provider};
+use crate::types::codex::{CodexEvent, CodexMessage};
+use crate::types::copilot::{
+    AssistantMessageData, CopilotEvent, ToolExecutionCompleteData, ToolExecutionStartData,
+};
+use crate::types::{ContentBlock, ConversationMessage};
+use crate::utils;
+use crate::vtab::{self, ColDef, TableFunc};
+use duckdb::core::DataChunkHandle;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+
+/// A tool invocation joined to its result, normalized across providers. The row
+/// is anchored on the request (`tool_use`), so a call still awaiting — or
+/// missing — its result appears with NULL result columns.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct ToolResultRow {
+    provider: String,
+    session_id: Option<String>,
+    tool_use_id: Option<String>,
+    tool_name: Option<String>,
+    tool_input: Option<String>,
+    result_content: Option<String>,
+    is_error: Option<bool>,
+}
+
+/// Accumulated request side of the join, before results are attached.
+struct PendingUse {
+    session_id: Option<String>,
+    tool_use_id: Option<String>,
+    tool_name: Option<String>,
+    tool_input: Option<String>,
+}
+
+pub struct ToolResults;
+
+impl ToolResults {
+    fn load_claude_rows(
+        base_path: &std::path::Path,
+    ) -> impl Iterator<Item = ToolResultRow> + Send {
+        utils::discover_conversation_files(base_path)
+            .into_iter()
+            .flat_map(|(_project_dir, _is_agent, file_path)| Self::claude_file_rows(&file_path))
+    }
+
+    fn claude_file_rows(file_path: &std::path::Path) -> Vec<ToolResultRow> {
+        let mut out = Vec::new();
+        {
+            let file_name = file_path
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let file_session_id = utils::extract_session_id_from_filename(&file_name);
+            let file = match std::fs::File::open(file_path) {
+                Ok(f) => f,
+                Err(_) => return out,
+            };
+
+            let mut uses: Vec<PendingUse> = Vec::new();
+            let mut results: HashMap<String, (Option<String>, Option<bool>)> = HashMap::new();
+
+            for line_result in BufReader::new(file).lines() {
+                let line = match line_result {
+                    Ok(l) => l,
+                    Err(_) => continue,
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let msg = match serde_json::from_str::<ConversationMessage>(&line) {
+                    Ok(m) => m,
+                    Err(_) => continue,
+                };
+                for block in Self::blocks_of(&msg) {
+                    match block {
+                        ContentBlock::ToolUse { id, name, input } => uses.push(PendingUse {
+                            session_id: Some(file_session_id.clone()),
+                            tool_use_id: id,
+                            tool_name: name,
+                            tool_input: input.map(|i| i.to_string()),
+                        }),
+                        ContentBlock::ToolResult {
+                            tool_use_id,
+                            content,
+                            is_error,
+                        } => {
+                            if let Some(id) = tool_use_id {
+                                results.insert(id, (content, is_error));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            Self::merge("claude", uses, results, &mut out);
+        }
+        out
+    }
+
+    /// Extract the content blocks from a conversation message regardless of role
+    /// — assistant blocks are already typed, user content is a raw array that we
+    /// re-parse so `tool_result` blocks carried on user turns are also seen.
+    fn blocks_of(msg: &ConversationMessage) -> Vec<ContentBlock> {
+        match msg {
+            ConversationMessage::Assistant(a) => a
+                .message
+                .as_ref()
+                .and_then(|m| m.content.clone())
+                .unwrap_or_default(),
+            ConversationMessage::User(u) => u
+                .message
+                .as_ref()
+                .and_then(|m| m.content.clone())
+                .and_then(|v| serde_json::from_value::<Vec<ContentBlock>>(v).ok())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn load_copilot_rows(
+        base_path: &std::path::Path,
+    ) -> impl Iterator<Item = ToolResultRow> + Send {
+        utils::discover_copilot_event_files(base_path)
+            .into_iter()
+            .flat_map(|(session_id, path)| Self::copilot_file_rows(session_id, path))
+    }
+
+    fn copilot_file_rows(session_id: String, path: std::path::PathBuf) -> Vec<ToolResultRow> {
+        let mut out = Vec::new();
+        {
+            let file = match std::fs::File::open(&path) {
+                Ok(f) => f,
+                Err(_) => return out,
+            };
+
+            let mut uses: Vec<PendingUse> = Vec::new();
+            let mut results: HashMap<String, (Option<String>, Option<bool>)> = HashMap::new();
+
+            for line_result in BufReader::new(file).lines() {
+                let line = match line_result {
+                    Ok(l) => l,
+                    Err(_) => continue,
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let event: CopilotEvent = match serde_json::from_str(&line) {
+                    Ok(e) => e,
+                    Err(_) => continue,
+                };
+                match event.event_type.as_str() {
+                    "assistant.message" => {
+                        let data: AssistantMessageData =
+                            serde_json::from_value(event.data).unwrap_or_default();
+                        for req in data.tool_requests.unwrap_or_default() {
+                            uses.push(PendingUse {
+                                session_id: Some(session_id.clone()),
+                                tool_use_id: req.tool_call_id,
+                                tool_name: req.name,
+                                tool_input: req.arguments.map(|a| a.to_string()),
+                            });
+                        }
+                    }
+                    "tool.execution_start" => {
+                        let data: ToolExecutionStartData =
+                            serde_json::from_value(event.data).unwrap_or_default();
+                        uses.push(PendingUse {
+                            session_id: Some(session_id.clone()),
+                            tool_use_id: data.tool_call_id,
+                            tool_name: data.tool_name,
+                            tool_input: data.arguments.map(|a| a.to_string()),
+                        });
+                    }
+                    "tool.execution_complete" => {
+                        let data: ToolExecutionCompleteData =
+                            serde_json::from_value(event.data).unwrap_or_default();
+                        if let Some(id) = data.tool_call_id {
+                            let is_error = data.success.map(|ok| !ok);
+                            let content = data.result.and_then(|r| r.content);
+                            results.insert(id, (content, is_error));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            // A tool.execution_start duplicates the assistant.message request for
+            // the same call id; keep the first sighting per id so each call is a
+            // single request row.
+            let mut seen = std::collections::HashSet::new();
+            uses.retain(|u| match &u.tool_use_id {
+                Some(id) => seen.insert(id.clone()),
+                None => true,
+            });
+
+            Self::merge("copilot", uses, results, &mut out);
+        }
+        out
+    }
+
+    fn load_codex_rows(
+        base_path: &std::path::Path,
+    ) -> impl Iterator<Item = ToolResultRow> + Send {
+        utils::discover_codex_session_files(base_path)
+            .into_iter()
+            .flat_map(|(thread_id, path)| Self::codex_file_rows(thread_id, path))
+    }
+
+    /// Codex rollouts record tool calls inline on assistant messages as
+    /// OpenAI-style `{ id, function: { name, arguments } }` entries, without a
+    /// separate result event, so each call surfaces as a request-anchored row
+    /// with NULL result columns.
+    fn codex_file_rows(thread_id: String, path: std::path::PathBuf) -> Vec<ToolResultRow> {
+        let mut out = Vec::new();
+        let file = match std::fs::File::open(&path) {
+            Ok(f) => f,
+            Err(_) => return out,
+        };
+        for line_result in BufReader::new(file).lines() {
+            let line = match line_result {
+                Ok(l) => l,
+                Err(_) => continue,
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Ok(CodexEvent::Message(CodexMessage { tool_calls, .. })) =
+                serde_json::from_str::<CodexEvent>(&line)
+            {
+                for call in tool_calls.unwrap_or_default() {
+                    let (tool_name, tool_input) = call
+                        .function
+                        .map(|f| (f.name, f.arguments))
+                        .unwrap_or((None, None));
+                    out.push(ToolResultRow {
+                        provider: "codex".to_string(),
+                        session_id: Some(thread_id.clone()),
+                        tool_use_id: call.id,
+                        tool_name,
+                        tool_input,
+                        result_content: None,
+                        is_error: None,
+                    });
+                }
+            }
+        }
+        out
+    }
+
+    /// Left-join the request side onto the collected results.
+    fn merge(
+        provider: &str,
+        uses: Vec<PendingUse>,
+        mut results: HashMap<String, (Option<String>, Option<bool>)>,
+        out: &mut Vec<ToolResultRow>,
+    ) {
+        for u in uses {
+            let (result_content, is_error) = u
+                .tool_use_id
+                .as_ref()
+                .and_then(|id| results.remove(id))
+                .unwrap_or((None, None));
+            out.push(ToolResultRow {
+                provider: provider.to_string(),
+                session_id: u.session_id,
+                tool_use_id: u.tool_use_id,
+                tool_name: u.tool_name,
+                tool_input: u.tool_input,
+                result_content,
+                is_error,
+            });
+        }
+        // Results with no matching request (unusual, but never drop them).
+        for (id, (content, is_error)) in results {
+            out.push(ToolResultRow {
+                provider: provider.to_string(),
+                session_id: None,
+                tool_use_id: Some(id),
+                tool_name: None,
+                tool_input: None,
+                result_content: content,
+                is_error,
+            });
+        }
+    }
+}
+
+impl TableFunc for ToolResults {
+    type Row = ToolResultRow;
+
+    fn columns() -> Vec<ColDef> {
+        vec![
+            vtab::varchar("provider"),
+            vtab::varchar("session_id"),
+            vtab::varchar("tool_use_id"),
+            vtab::varchar("tool_name"),
+            vtab::varchar("tool_input"),
+            vtab::varchar("result_content"),
+            vtab::boolean("is_error"),
+        ]
+    }
+
+    fn load_rows(
+        path: Option<&str>,
+        source: Option<&str>,
+    ) -> Box<dyn Iterator<Item = ToolResultRow> + Send> {
+        let base_path = utils::resolve_data_path(path);
+        match detect::resolve_provider(&base_path, source) {
+            Provider::Claude => Box::new(Self::load_claude_rows(&base_path)),
+            Provider::Copilot => Box::new(Self::load_copilot_rows(&base_path)),
+            Provider::Codex => Box::new(Self::load_codex_rows(&base_path)),
+            Provider::Unknown => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn write_row(output: &mut DataChunkHandle, idx: usize, row: &ToolResultRow) {
+        vtab::set_varchar(output, 0, idx, &row.provider);
+        vtab::set_varchar_opt(output, 1, idx, row.session_id.as_deref());
+        vtab::set_varchar_opt(output, 2, idx, row.tool_use_id.as_deref());
+        vtab::set_varchar_opt(output, 3, idx, row.tool_name.as_deref());
+        vtab::set_varchar_opt(output, 4, idx, row.tool_input.as_deref());
+        vtab::set_varchar_opt(output, 5, idx, row.result_content.as_deref());
+        vtab::set_bool_opt(output, 6, idx, row.is_error);
+    }
+}
@@ -1,22 +1,146 @@
-use serde::Deserialize;
+use serde::de;
+use serde::{Deserialize, Deserializer};
+
+pub mod codex;
+pub mod copilot;
+
+// ─── Typed category enums with Unknown fallback ───
+//
+// Several fields are semantically a small closed set but arrive as free-form
+// strings. Modeling them as enums with an explicit `Unknown(String)` lets
+// queries GROUP BY a stable category while still round-tripping a value the
+// CLI introduces later: `canonical()` returns the normalized spelling for
+// known cases and the original string for unknown ones, so the raw value is
+// never lost.
+
+/// Status of a todo item.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TodoStatus {
+    Pending,
+    InProgress,
+    Completed,
+    Unknown(String),
+}
+
+impl TodoStatus {
+    pub fn canonical(&self) -> String {
+        match self {
+            TodoStatus::Pending => "pending".to_string(),
+            TodoStatus::InProgress => "in_progress".to_string(),
+            TodoStatus::Completed => "completed".to_string(),
+            TodoStatus::Unknown(s) => s.clone(),
+        }
+    }
+}
+
+impl From<String> for TodoStatus {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "pending" => TodoStatus::Pending,
+            "in_progress" => TodoStatus::InProgress,
+            "completed" => TodoStatus::Completed,
+            _ => TodoStatus::Unknown(s),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TodoStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(TodoStatus::from(String::deserialize(deserializer)?))
+    }
+}
+
+/// Reason an assistant turn stopped generating.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StopReason {
+    EndTurn,
+    MaxTokens,
+    ToolUse,
+    StopSequence,
+    Unknown(String),
+}
+
+impl StopReason {
+    pub fn canonical(&self) -> String {
+        match self {
+            StopReason::EndTurn => "end_turn".to_string(),
+            StopReason::MaxTokens => "max_tokens".to_string(),
+            StopReason::ToolUse => "tool_use".to_string(),
+            StopReason::StopSequence => "stop_sequence".to_string(),
+            StopReason::Unknown(s) => s.clone(),
+        }
+    }
+}
+
+impl From<String> for StopReason {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "end_turn" => StopReason::EndTurn,
+            "max_tokens" => StopReason::MaxTokens,
+            "tool_use" => StopReason::ToolUse,
+            "stop_sequence" => StopReason::StopSequence,
+            _ => StopReason::Unknown(s),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for StopReason {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(StopReason::from(String::deserialize(deserializer)?))
+    }
+}
 
 // ─── Conversation Messages (JSONL) ───
 
-#[derive(Deserialize, Debug, Clone)]
-#[serde(tag = "type")]
+#[derive(Debug, Clone)]
 pub enum ConversationMessage {
-    #[serde(rename = "user")]
     User(UserMessage),
-    #[serde(rename = "assistant")]
     Assistant(AssistantMessage),
-    #[serde(rename = "system")]
     System(SystemMessage),
-    #[serde(rename = "file-history-snapshot")]
     FileHistorySnapshot {},
-    #[serde(rename = "queue-operation")]
     QueueOperation(QueueOperationMessage),
-    #[serde(rename = "summary")]
     Summary(SummaryMessage),
+    /// Forward-compatibility catch-all: a top-level message `type` this build
+    /// does not yet model. The original payload is retained in `raw` so no row
+    /// is dropped and the new event kind stays queryable until a typed variant
+    /// is added.
+    Unknown {
+        type_name: String,
+        raw: serde_json::Value,
+    },
+}
+
+impl<'de> Deserialize<'de> for ConversationMessage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Rather than a closed `#[serde(tag = "type")]` set — which errors and
+        // drops the line on any new type — read the value first, dispatch on
+        // the `type` tag, and fall back to `Unknown` keeping the raw payload.
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let type_name = value
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let from = |v: serde_json::Value| serde_json::from_value(v).map_err(de::Error::custom);
+        Ok(match type_name.as_str() {
+            "user" => ConversationMessage::User(from(value)?),
+            "assistant" => ConversationMessage::Assistant(from(value)?),
+            "system" => ConversationMessage::System(from(value)?),
+            "file-history-snapshot" => ConversationMessage::FileHistorySnapshot {},
+            "queue-operation" => ConversationMessage::QueueOperation(from(value)?),
+            "summary" => ConversationMessage::Summary(from(value)?),
+            _ => ConversationMessage::Unknown { type_name, raw: value },
+        })
+    }
 }
 
 #[derive(Deserialize, Debug, Clone, Default)]
@@ -25,7 +149,8 @@ pub struct BaseFields {
     pub uuid: Option<String>,
     #[serde(rename = "parentUuid")]
     pub parent_uuid: Option<String>,
-    pub timestamp: Option<String>,
+    #[serde(deserialize_with = "crate::timeutil::deserialize_opt")]
+    pub timestamp: crate::timeutil::Timestamp,
     #[serde(rename = "sessionId")]
     pub session_id: Option<String>,
     pub cwd: Option<String>,
@@ -58,25 +183,109 @@ pub struct AssistantMessage {
 pub struct AssistantMessageContent {
     pub model: Option<String>,
     pub content: Option<Vec<ContentBlock>>,
-    pub stop_reason: Option<String>,
+    pub stop_reason: Option<StopReason>,
     pub usage: Option<UsageInfo>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
-#[serde(tag = "type")]
+#[derive(Debug, Clone)]
 pub enum ContentBlock {
-    #[serde(rename = "text")]
     Text { text: String },
-    #[serde(rename = "thinking")]
-    Thinking {},
-    #[serde(rename = "tool_use")]
+    Thinking {
+        text: String,
+        signature: Option<String>,
+    },
     ToolUse {
         id: Option<String>,
         name: Option<String>,
         input: Option<serde_json::Value>,
     },
-    #[serde(rename = "tool_result")]
-    ToolResult {},
+    ToolResult {
+        tool_use_id: Option<String>,
+        content: Option<String>,
+        is_error: Option<bool>,
+    },
+    /// Catch-all for block types this build does not model yet (e.g.
+    /// `redacted_thinking`, `server_tool_use`, `web_search_tool_result`). The
+    /// raw block is preserved so nothing is lost on a CLI upgrade.
+    Unknown {
+        type_name: String,
+        raw: serde_json::Value,
+    },
+}
+
+impl<'de> Deserialize<'de> for ContentBlock {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let type_name = value
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default()
+            .to_string();
+        match type_name.as_str() {
+            "text" => Ok(ContentBlock::Text {
+                text: value
+                    .get("text")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+            }),
+            "thinking" => Ok(ContentBlock::Thinking {
+                text: value
+                    .get("thinking")
+                    .or_else(|| value.get("text"))
+                    .and_then(|t| t.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                signature: value
+                    .get("signature")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+            }),
+            "tool_use" => Ok(ContentBlock::ToolUse {
+                id: value.get("id").and_then(|v| v.as_str()).map(String::from),
+                name: value.get("name").and_then(|v| v.as_str()).map(String::from),
+                input: value.get("input").cloned(),
+            }),
+            "tool_result" => Ok(ContentBlock::ToolResult {
+                tool_use_id: value
+                    .get("tool_use_id")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                content: value.get("content").and_then(flatten_content_text),
+                is_error: value.get("is_error").and_then(|v| v.as_bool()),
+            }),
+            _ => Ok(ContentBlock::Unknown { type_name, raw: value }),
+        }
+    }
+}
+
+/// A `tool_result` block's `content` is either a bare string or a list of
+/// `{ type: "text" | "image", … }` blocks. Collapse both into the text a query
+/// would want, joining multiple text blocks and noting images as `[image]` so
+/// their presence is not silently dropped.
+fn flatten_content_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Array(blocks) => {
+            let parts: Vec<String> = blocks
+                .iter()
+                .filter_map(|b| match b.get("type").and_then(|t| t.as_str()) {
+                    Some("text") => b.get("text").and_then(|t| t.as_str()).map(String::from),
+                    Some("image") => Some("[image]".to_string()),
+                    _ => None,
+                })
+                .collect();
+            if parts.is_empty() {
+                None
+            } else {
+                Some(parts.join("\n"))
+            }
+        }
+        _ => None,
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -125,7 +334,7 @@ pub struct HistoryEntry {
 #[derive(Deserialize, Debug, Clone)]
 pub struct TodoItem {
     pub content: Option<String>,
-    pub status: Option<String>,
+    pub status: Option<TodoStatus>,
     #[serde(rename = "activeForm")]
     pub active_form: Option<String>,
 }
@@ -0,0 +1,73 @@
+use serde::Deserialize;
+
+/// A line in a Codex rollout transcript. Codex writes one JSON object per line,
+/// each tagged with a `type` discriminator, mirroring the thread/message/run
+/// shape the ChatGPT CLI emits.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum CodexEvent {
+    /// Thread/session envelope written at the start of a rollout.
+    #[serde(rename = "thread")]
+    Thread(ThreadMeta),
+    /// A user or assistant message.
+    #[serde(rename = "message")]
+    Message(CodexMessage),
+    /// A model run carrying token usage.
+    #[serde(rename = "run")]
+    Run(CodexRun),
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ThreadMeta {
+    pub id: Option<String>,
+    pub cwd: Option<String>,
+    pub model: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CodexMessage {
+    pub id: Option<String>,
+    /// `user` or `assistant`.
+    pub role: Option<String>,
+    pub content: Option<String>,
+    #[serde(rename = "toolCalls")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+}
+
+/// An OpenAI-style tool call: `{ id, function: { name, arguments } }`.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ToolCall {
+    pub id: Option<String>,
+    pub function: Option<ToolCallFunction>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ToolCallFunction {
+    pub name: Option<String>,
+    /// Raw JSON-encoded argument string, as OpenAI emits it.
+    pub arguments: Option<String>,
+}
+
+/// Per-run token usage.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CodexRun {
+    pub usage: Option<CodexUsage>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CodexUsage {
+    #[serde(rename = "promptTokens")]
+    pub prompt_tokens: Option<i64>,
+    #[serde(rename = "completionTokens")]
+    pub completion_tokens: Option<i64>,
+    #[serde(rename = "totalTokens")]
+    pub total_tokens: Option<i64>,
+}
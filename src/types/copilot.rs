@@ -1,12 +1,19 @@
 use serde::Deserialize;
 
 /// Common envelope for all Copilot CLI events.
+///
+/// `event_type` is kept as a free-form string and `data` as an untyped
+/// `Value`, so an unrecognized event kind round-trips its payload untouched
+/// rather than failing the line — the same forward-compatibility guarantee the
+/// Claude `ConversationMessage`/`ContentBlock` enums get via their `Unknown`
+/// variants.
 #[derive(Deserialize, Debug, Clone)]
 pub struct CopilotEvent {
     #[serde(rename = "type")]
     pub event_type: String,
     pub id: Option<String>,
-    pub timestamp: Option<String>,
+    #[serde(default, deserialize_with = "crate::timeutil::deserialize_opt")]
+    pub timestamp: crate::timeutil::Timestamp,
     #[serde(rename = "parentId")]
     pub parent_id: Option<String>,
     #[serde(default)]
@@ -114,12 +121,56 @@ pub struct ReasoningData {
     pub content: Option<String>,
 }
 
+/// Category of a session error, with an `Unknown` fallback so a value the CLI
+/// introduces later still round-trips via `canonical()` instead of being lost.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorType {
+    RateLimit,
+    Timeout,
+    Cancelled,
+    Network,
+    Unknown(String),
+}
+
+impl ErrorType {
+    pub fn canonical(&self) -> String {
+        match self {
+            ErrorType::RateLimit => "rate_limit".to_string(),
+            ErrorType::Timeout => "timeout".to_string(),
+            ErrorType::Cancelled => "cancelled".to_string(),
+            ErrorType::Network => "network".to_string(),
+            ErrorType::Unknown(s) => s.clone(),
+        }
+    }
+}
+
+impl From<String> for ErrorType {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "rate_limit" => ErrorType::RateLimit,
+            "timeout" => ErrorType::Timeout,
+            "cancelled" => ErrorType::Cancelled,
+            "network" => ErrorType::Network,
+            _ => ErrorType::Unknown(s),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(ErrorType::from(String::deserialize(deserializer)?))
+    }
+}
+
 /// Parsed from session.error data.
 #[derive(Deserialize, Debug, Clone, Default)]
 #[serde(default)]
 pub struct SessionErrorData {
     #[serde(rename = "errorType")]
-    pub error_type: Option<String>,
+    pub error_type: Option<ErrorType>,
     pub message: Option<String>,
 }
 
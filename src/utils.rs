@@ -1,5 +1,7 @@
 use std::path::{Path, PathBuf};
 
+pub mod cache;
+
 /// Resolve a data directory path.
 /// If path is provided, expand ~ and return it.
 /// If no path, default to ~/.claude (legacy default).
@@ -71,6 +73,61 @@ pub fn discover_conversation_files(base_path: &Path) -> Vec<(String, bool, PathB
     results
 }
 
+/// Resolve a worker-thread count. `0` or an absent value means "use available
+/// parallelism"; `1` forces sequential parsing (the historical behavior).
+pub fn resolve_threads(threads: Option<i64>) -> usize {
+    match threads {
+        Some(n) if n >= 1 => n as usize,
+        _ => std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+    }
+}
+
+/// Parse `items` in parallel across up to `threads` workers, returning the
+/// results in the original input order.
+///
+/// Files discovered by the `discover_*` helpers are independent of one another,
+/// so they can be parsed concurrently; re-ordering the merged results by the
+/// input index afterwards preserves the deterministic `(file_index, …)` ordering
+/// the VTabs rely on. With `threads == 1` this degrades to a plain sequential
+/// map.
+pub fn parallel_map<T, R, F>(items: Vec<T>, threads: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(usize, &T) -> R + Sync,
+{
+    if threads <= 1 || items.len() <= 1 {
+        return items.iter().enumerate().map(|(i, it)| f(i, it)).collect();
+    }
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    let next = AtomicUsize::new(0);
+    let slots: Vec<Mutex<Option<R>>> = (0..items.len()).map(|_| Mutex::new(None)).collect();
+    let worker_count = std::cmp::min(threads, items.len());
+
+    std::thread::scope(|scope| {
+        for _ in 0..worker_count {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= items.len() {
+                    break;
+                }
+                let result = f(i, &items[i]);
+                *slots[i].lock().unwrap() = Some(result);
+            });
+        }
+    });
+
+    slots
+        .into_iter()
+        .map(|m| m.into_inner().unwrap().expect("every slot computed"))
+        .collect()
+}
+
 /// Decode project path: `-Users-username-project` → `/Users/username/project`
 pub fn decode_project_path(encoded: &str) -> String {
     if encoded.starts_with('-') {
@@ -115,6 +172,55 @@ pub fn discover_plan_files(base_path: &Path) -> Vec<PathBuf> {
     results
 }
 
+/// Discover plan files under `plans/`, optionally walking subdirectories and
+/// matching by glob. Returns `(absolute_path, relative_path)` pairs where the
+/// relative path is taken from the `plans/` root, letting callers expose a
+/// subproject column. With `recursive == false` and no `pattern` this reduces to
+/// the flat `*.md` scan of [`discover_plan_files`].
+pub fn discover_plan_files_glob(
+    base_path: &Path,
+    pattern: Option<&str>,
+    recursive: bool,
+) -> Vec<(PathBuf, String)> {
+    let plans_dir = base_path.join("plans");
+    let mut results = Vec::new();
+
+    if !plans_dir.is_dir() {
+        return results;
+    }
+
+    let matcher = pattern.and_then(|p| glob::Pattern::new(p).ok());
+
+    let mut stack = vec![plans_dir.clone()];
+    while let Some(dir) = stack.pop() {
+        let entries = std::fs::read_dir(&dir).into_iter().flatten().filter_map(|e| e.ok());
+        for entry in entries {
+            let path = entry.path();
+            if path.is_dir() {
+                if recursive {
+                    stack.push(path);
+                }
+                continue;
+            }
+            let rel = path
+                .strip_prefix(&plans_dir)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .to_string();
+            let matched = match &matcher {
+                Some(p) => p.matches(&rel),
+                None => path.extension().map_or(false, |ext| ext == "md"),
+            };
+            if matched {
+                results.push((path, rel));
+            }
+        }
+    }
+
+    results.sort_by(|a, b| a.1.cmp(&b.1));
+    results
+}
+
 /// Discover todo JSON files under todos/ directory.
 /// Returns (session_id, agent_id, file_path) tuples.
 pub fn discover_todo_files(base_path: &Path) -> Vec<(String, String, PathBuf)> {
@@ -150,6 +256,40 @@ pub fn discover_todo_files(base_path: &Path) -> Vec<(String, String, PathBuf)> {
     results
 }
 
+/// Discover Codex rollout transcripts under `sessions/` (or `threads/`).
+/// Each thread is a `<thread-id>.jsonl` rollout file. Returns
+/// (thread_id, file_path) tuples sorted by thread id.
+pub fn discover_codex_session_files(base_path: &Path) -> Vec<(String, PathBuf)> {
+    let dir = {
+        let sessions = base_path.join("sessions");
+        if sessions.is_dir() {
+            sessions
+        } else {
+            base_path.join("threads")
+        }
+    };
+    let mut results = Vec::new();
+
+    if !dir.is_dir() {
+        return results;
+    }
+
+    let mut files: Vec<_> = std::fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().map_or(false, |ext| ext == "jsonl"))
+        .collect();
+    files.sort_by_key(|e| e.file_name());
+
+    for f in files {
+        let fname = f.file_name().to_string_lossy().to_string();
+        let thread_id = fname.strip_suffix(".jsonl").unwrap_or(&fname).to_string();
+        results.push((thread_id, f.path()));
+    }
+    results
+}
+
 /// Get the history.jsonl path.
 pub fn history_file_path(base_path: &Path) -> PathBuf {
     base_path.join("history.jsonl")
@@ -0,0 +1,288 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// On-disk format version. Bump this whenever the `HistoryRow`/`TodoRow`/
+/// conversation schema (or anything else serialized into a cache blob) changes
+/// so that stale blobs written by an older build are discarded rather than
+/// deserialized into the wrong shape.
+const FORMAT_VERSION: u8 = 1;
+
+/// Magic prefix so a truncated or unrelated file is rejected before we trust
+/// any offsets inside it.
+const MAGIC: &[u8; 4] = b"DCEC";
+
+/// Default sidecar file name, written alongside the data directory.
+const CACHE_FILE_NAME: &str = ".duckdb-ext-cache";
+
+/// One cache entry describing a single parsed source file. Mirrors the "v2
+/// dirstate" layout: stat metadata plus a `(offset, len)` slice into the packed
+/// blob region that follows the entry table.
+struct Entry {
+    path_hash: u64,
+    mtime_secs: i64,
+    mtime_nanos: u32,
+    /// Set when the filesystem reported a whole-second mtime (nanos == 0), which
+    /// may mean the clock only has second granularity. When set, the sub-second
+    /// component is ignored and freshness is judged on whole seconds and size.
+    second_ambiguous: bool,
+    size: u64,
+    blob_offset: u64,
+    blob_len: u64,
+}
+
+/// Stat result for a source file, resolved once per discovery pass.
+pub struct SourceStat {
+    pub mtime_secs: i64,
+    pub mtime_nanos: u32,
+    pub second_ambiguous: bool,
+    pub size: u64,
+}
+
+/// Stat a path into the fields the cache compares against. Returns `None` when
+/// the file cannot be stat'd, in which case the caller should parse normally.
+pub fn stat_source(path: &Path) -> Option<SourceStat> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?;
+    let dur = mtime.duration_since(UNIX_EPOCH).ok()?;
+    let nanos = dur.subsec_nanos();
+    Some(SourceStat {
+        mtime_secs: dur.as_secs() as i64,
+        mtime_nanos: nanos,
+        second_ambiguous: nanos == 0,
+        size: meta.len(),
+    })
+}
+
+fn hash_path(path: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A persistent, incremental parse cache keyed by file mtime/size.
+///
+/// The cache materializes parsed rows to a compact on-disk sidecar so repeated
+/// queries over a large, mostly-static `~/.claude` tree avoid re-reading and
+/// re-parsing every file. Rows are bincode-encoded per source file into a
+/// packed blob region; an entry table up front records the stat metadata used
+/// to decide whether a blob is still fresh.
+pub struct ParseCache {
+    cache_path: PathBuf,
+    entries: Vec<Entry>,
+    blob: Vec<u8>,
+    /// Whether any entry was added or replaced since load, i.e. a rewrite is due.
+    dirty: bool,
+}
+
+impl ParseCache {
+    /// Open (or start) the cache for the given data directory. A corrupt or
+    /// truncated sidecar is treated as empty so we degrade to full parsing
+    /// rather than erroring.
+    pub fn open(base_path: &Path) -> Self {
+        let cache_path = base_path.join(CACHE_FILE_NAME);
+        match Self::read_file(&cache_path) {
+            Ok((entries, blob)) => ParseCache {
+                cache_path,
+                entries,
+                blob,
+                dirty: false,
+            },
+            Err(_) => ParseCache {
+                cache_path,
+                entries: Vec::new(),
+                blob: Vec::new(),
+                dirty: false,
+            },
+        }
+    }
+
+    fn read_file(path: &Path) -> io::Result<(Vec<Entry>, Vec<u8>)> {
+        let mut bytes = Vec::new();
+        std::fs::File::open(path)?.read_to_end(&mut bytes)?;
+
+        let mut cur = Cursor::new(&bytes);
+        let mut magic = [0u8; 4];
+        cur.read_exact(&mut magic)?;
+        if &magic != MAGIC || cur.read_u8()? != FORMAT_VERSION {
+            return Err(corrupt());
+        }
+        let count = cur.read_u32()? as usize;
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            entries.push(Entry {
+                path_hash: cur.read_u64()?,
+                mtime_secs: cur.read_u64()? as i64,
+                mtime_nanos: cur.read_u32()?,
+                second_ambiguous: cur.read_u8()? != 0,
+                size: cur.read_u64()?,
+                blob_offset: cur.read_u64()?,
+                blob_len: cur.read_u64()?,
+            });
+        }
+        let blob = bytes[cur.pos..].to_vec();
+        // Validate every slice lands inside the blob region.
+        for e in &entries {
+            let end = e.blob_offset.checked_add(e.blob_len).ok_or_else(corrupt)?;
+            if end as usize > blob.len() {
+                return Err(corrupt());
+            }
+        }
+        Ok((entries, blob))
+    }
+
+    fn find(&self, path_hash: u64) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.path_hash == path_hash)
+    }
+
+    /// Return cached rows for `path` when the recorded stat still matches.
+    ///
+    /// A match requires identical size and whole-second mtime. The sub-second
+    /// component must also match, unless either the cached or the current stat
+    /// is second-ambiguous: then it is not compared, since a coarse clock makes
+    /// it meaningless, and the whole-second match alone is accepted.
+    pub fn lookup<R>(&self, path: &Path, stat: &SourceStat) -> Option<Vec<R>>
+    where
+        R: DeserializeOwned,
+    {
+        let entry = self.find(hash_path(path))?;
+        let size_ok = entry.size == stat.size;
+        let secs_ok = entry.mtime_secs == stat.mtime_secs;
+        let sub_ok = if entry.second_ambiguous || stat.second_ambiguous {
+            true // only second-granularity is trustworthy here
+        } else {
+            entry.mtime_nanos == stat.mtime_nanos
+        };
+        if !(size_ok && secs_ok && sub_ok) {
+            return None;
+        }
+        let start = entry.blob_offset as usize;
+        let end = start + entry.blob_len as usize;
+        bincode::deserialize::<Vec<R>>(&self.blob[start..end]).ok()
+    }
+
+    /// Stage freshly-parsed rows so they are written on the next [`flush`].
+    ///
+    /// [`flush`]: ParseCache::flush
+    pub fn stage<R>(&mut self, path: &Path, stat: &SourceStat, rows: &[R])
+    where
+        R: Serialize,
+    {
+        if let Ok(encoded) = bincode::serialize(rows) {
+            self.insert(hash_path(path), stat, encoded);
+        }
+    }
+
+    /// Return cached rows for `path` if fresh, else parse with `parse` and stage
+    /// the result for the next rewrite.
+    pub fn load_or_parse<R, F>(&mut self, path: &Path, stat: &SourceStat, parse: F) -> Vec<R>
+    where
+        R: Serialize + DeserializeOwned,
+        F: FnOnce() -> Vec<R>,
+    {
+        if let Some(rows) = self.lookup(path, stat) {
+            return rows;
+        }
+        let rows = parse();
+        self.stage(path, stat, &rows);
+        rows
+    }
+
+    fn insert(&mut self, path_hash: u64, stat: &SourceStat, encoded: Vec<u8>) {
+        let blob_offset = self.blob.len() as u64;
+        let blob_len = encoded.len() as u64;
+        self.blob.extend_from_slice(&encoded);
+        self.entries.retain(|e| e.path_hash != path_hash);
+        self.entries.push(Entry {
+            path_hash,
+            mtime_secs: stat.mtime_secs,
+            mtime_nanos: stat.mtime_nanos,
+            second_ambiguous: stat.second_ambiguous,
+            size: stat.size,
+            blob_offset,
+            blob_len,
+        });
+        self.dirty = true;
+    }
+
+    /// Atomically rewrite the sidecar if anything changed since it was opened.
+    /// Writes to a temp file and renames so a reader never sees a half-written
+    /// cache. Failures are swallowed: the cache is an optimization, never a
+    /// correctness dependency.
+    pub fn flush(&self) {
+        if !self.dirty {
+            return;
+        }
+        let _ = self.write_atomic();
+    }
+
+    fn write_atomic(&self) -> io::Result<()> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(MAGIC);
+        buf.push(FORMAT_VERSION);
+        buf.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        for e in &self.entries {
+            buf.extend_from_slice(&e.path_hash.to_le_bytes());
+            buf.extend_from_slice(&(e.mtime_secs as u64).to_le_bytes());
+            buf.extend_from_slice(&e.mtime_nanos.to_le_bytes());
+            buf.push(e.second_ambiguous as u8);
+            buf.extend_from_slice(&e.size.to_le_bytes());
+            buf.extend_from_slice(&e.blob_offset.to_le_bytes());
+            buf.extend_from_slice(&e.blob_len.to_le_bytes());
+        }
+        buf.extend_from_slice(&self.blob);
+
+        let tmp = self.cache_path.with_extension("tmp");
+        std::fs::File::create(&tmp)?.write_all(&buf)?;
+        std::fs::rename(&tmp, &self.cache_path)
+    }
+}
+
+fn corrupt() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "corrupt cache")
+}
+
+/// Minimal little-endian reader over an in-memory byte slice, so the format
+/// code above stays free of an extra dependency.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Cursor { bytes, pos: 0 }
+    }
+
+    fn read_exact(&mut self, out: &mut [u8]) -> io::Result<()> {
+        let end = self.pos + out.len();
+        if end > self.bytes.len() {
+            return Err(corrupt());
+        }
+        out.copy_from_slice(&self.bytes[self.pos..end]);
+        self.pos = end;
+        Ok(())
+    }
+
+    fn read_u8(&mut self) -> io::Result<u8> {
+        let mut b = [0u8; 1];
+        self.read_exact(&mut b)?;
+        Ok(b[0])
+    }
+
+    fn read_u32(&mut self) -> io::Result<u32> {
+        let mut b = [0u8; 4];
+        self.read_exact(&mut b)?;
+        Ok(u32::from_le_bytes(b))
+    }
+
+    fn read_u64(&mut self) -> io::Result<u64> {
+        let mut b = [0u8; 8];
+        self.read_exact(&mut b)?;
+        Ok(u64::from_le_bytes(b))
+    }
+}
@@ -5,7 +5,7 @@ use duckdb::{
 };
 use std::ffi::CString;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Mutex;
+use std::sync::Arc;
 
 // ─── Column definition helpers ───
 
@@ -13,6 +13,9 @@ pub enum ColType {
     Varchar,
     Bigint,
     Boolean,
+    /// Microsecond-resolution timestamp with time zone, written via
+    /// [`set_timestamp_opt`] / [`set_timestamp`].
+    Timestamp,
 }
 
 pub struct ColDef {
@@ -32,6 +35,20 @@ pub fn boolean(name: &'static str) -> ColDef {
     ColDef { name, typ: ColType::Boolean }
 }
 
+pub fn timestamp(name: &'static str) -> ColDef {
+    ColDef { name, typ: ColType::Timestamp }
+}
+
+/// Central `ColType` → `LogicalTypeHandle` mapping, used by [`GenericVTab::bind`].
+pub fn map_type(typ: &ColType) -> LogicalTypeHandle {
+    match typ {
+        ColType::Varchar => LogicalTypeHandle::from(LogicalTypeId::Varchar),
+        ColType::Bigint => LogicalTypeHandle::from(LogicalTypeId::Bigint),
+        ColType::Boolean => LogicalTypeHandle::from(LogicalTypeId::Boolean),
+        ColType::Timestamp => LogicalTypeHandle::from(LogicalTypeId::TimestampTz),
+    }
+}
+
 // ─── Vector output helpers ───
 
 pub fn set_varchar(output: &mut DataChunkHandle, col: usize, row: usize, val: &str) {
@@ -52,6 +69,14 @@ pub fn set_bool(output: &mut DataChunkHandle, col: usize, row: usize, val: bool)
     vec.as_mut_slice::<bool>()[row] = val;
 }
 
+pub fn set_bool_opt(output: &mut DataChunkHandle, col: usize, row: usize, val: Option<bool>) {
+    let mut vec = output.flat_vector(col);
+    match val {
+        Some(v) => vec.as_mut_slice::<bool>()[row] = v,
+        None => vec.set_null(row),
+    }
+}
+
 pub fn set_i64(output: &mut DataChunkHandle, col: usize, row: usize, val: i64) {
     let mut vec = output.flat_vector(col);
     vec.as_mut_slice::<i64>()[row] = val;
@@ -65,31 +90,82 @@ pub fn set_i64_opt(output: &mut DataChunkHandle, col: usize, row: usize, val: Op
     }
 }
 
+/// Write an optional microseconds-since-epoch timestamp into a `TimestampTz`
+/// column, storing NULL when the value is absent or could not be parsed.
+pub fn set_timestamp_opt(output: &mut DataChunkHandle, col: usize, row: usize, micros: Option<i64>) {
+    let mut vec = output.flat_vector(col);
+    match micros {
+        Some(v) => vec.as_mut_slice::<i64>()[row] = v,
+        None => vec.set_null(row),
+    }
+}
+
+/// Write a microseconds-since-epoch timestamp into a `TimestampTz` column.
+pub fn set_timestamp(output: &mut DataChunkHandle, col: usize, row: usize, micros: i64) {
+    let mut vec = output.flat_vector(col);
+    vec.as_mut_slice::<i64>()[row] = micros;
+}
+
 // ─── Generic VTab implementation ───
 
 /// Trait that each table function implements to define its schema, loading, and row writing.
+///
+/// `GenericVTab` does not stream: [`bind`](GenericVTab::bind) always drains
+/// `load_rows` into one resident `Arc<[Row]>` before `func` runs a row, because
+/// the parallel range-partitioned scan needs an indexable backing store every
+/// worker thread can read concurrently. That parallel scan is the deliberate
+/// tradeoff for this framework — bounded, one-batch-plus-one-file memory is not
+/// a property of `GenericVTab` and isn't going to become one without giving up
+/// the parallel scan. `ReadConversationsVTab` (in `conversations.rs`) is the
+/// implementation that actually bounds memory: it streams off a file work
+/// queue, parsing a small batch of files at a time on a worker pool, instead of
+/// pre-loading into a slice. A
+/// generic function that needs bounded memory more than it needs a parallel
+/// scan should follow that bespoke pattern rather than `GenericVTab`.
 pub trait TableFunc: Sized + 'static {
-    type Row: Send + 'static;
+    type Row: Send + Sync + 'static;
 
     fn columns() -> Vec<ColDef>;
-    fn load_rows(path: Option<&str>) -> Vec<Self::Row>;
+
+    /// Produce the function's rows as an iterator. Yielding per file (rather
+    /// than building one giant `Vec` up front) keeps *peak* memory during
+    /// construction to one file's parse plus the growing result — but
+    /// `GenericVTab::bind` immediately drains this iterator into a shared
+    /// slice (see the trait-level note above), so the iterator return type
+    /// only buys lower peak memory during load, not bounded steady-state
+    /// memory during the scan.
+    fn load_rows(
+        path: Option<&str>,
+        source: Option<&str>,
+    ) -> Box<dyn Iterator<Item = Self::Row> + Send>;
+
     fn write_row(output: &mut DataChunkHandle, idx: usize, row: &Self::Row);
 }
 
 #[repr(C)]
-pub struct GenericBindData<R: Send + 'static> {
-    rows: Mutex<Vec<R>>,
+pub struct GenericBindData<R: Send + Sync + 'static> {
+    /// The fully-loaded rows, shared immutably with every scan thread. The lazy
+    /// producer from [`TableFunc::load_rows`] is drained into a slice here
+    /// because the parallel scan partitions rows by index range — which needs a
+    /// materialized, indexable backing store rather than a single cursor.
+    rows: Arc<[R]>,
 }
 
 #[repr(C)]
 pub struct GenericInitData {
-    offset: AtomicUsize,
+    /// Lock-free cursor shared by all scan threads. Each `func` call claims a
+    /// disjoint `[start, start + CHUNK)` range with a single `fetch_add`, so no
+    /// mutex is taken on the hot path.
+    next: AtomicUsize,
 }
 
 pub struct GenericVTab<T: TableFunc>(std::marker::PhantomData<T>);
 
-/// Resolve the optional `path` named parameter from bind info.
-fn resolve_path(bind: &BindInfo) -> Option<String> {
+/// Resolve the effective path from bind info, treating the positional argument
+/// and the `path` named parameter uniformly (named takes precedence). Shared by
+/// the generic framework and bespoke table functions so path handling stays
+/// consistent across `read_*` functions.
+pub fn resolve_path(bind: &BindInfo) -> Option<String> {
     let named = bind.get_named_parameter("path").map(|v| v.to_string());
     let positional = if bind.get_parameter_count() > 0 {
         let p = bind.get_parameter(0).to_string();
@@ -106,48 +182,60 @@ impl<T: TableFunc> VTab for GenericVTab<T> {
 
     fn bind(bind: &BindInfo) -> Result<Self::BindData, Box<dyn std::error::Error>> {
         for col in T::columns() {
-            let logical_type = match col.typ {
-                ColType::Varchar => LogicalTypeHandle::from(LogicalTypeId::Varchar),
-                ColType::Bigint => LogicalTypeHandle::from(LogicalTypeId::Bigint),
-                ColType::Boolean => LogicalTypeHandle::from(LogicalTypeId::Boolean),
-            };
-            bind.add_result_column(col.name, logical_type);
+            bind.add_result_column(col.name, map_type(&col.typ));
         }
 
         let path = resolve_path(bind);
-        let rows = T::load_rows(path.as_deref());
-        Ok(GenericBindData { rows: Mutex::new(rows) })
+        let source = bind.get_named_parameter("source").map(|v| v.to_string());
+        let rows: Arc<[T::Row]> = T::load_rows(path.as_deref(), source.as_deref())
+            .collect::<Vec<_>>()
+            .into();
+        Ok(GenericBindData { rows })
     }
 
-    fn init(_: &InitInfo) -> Result<Self::InitData, Box<dyn std::error::Error>> {
-        Ok(GenericInitData { offset: AtomicUsize::new(0) })
+    fn init(init: &InitInfo) -> Result<Self::InitData, Box<dyn std::error::Error>> {
+        // Advertise one worker per available core, so DuckDB fans the scan out
+        // across threads that all share the immutable row slice. The row count
+        // does not lower this number; a worker that finds no range left emits
+        // an empty chunk.
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        init.set_max_threads(threads as u64);
+        Ok(GenericInitData {
+            next: AtomicUsize::new(0),
+        })
     }
 
     fn func(
         func: &TableFunctionInfo<Self>,
         output: &mut DataChunkHandle,
     ) -> Result<(), Box<dyn std::error::Error>> {
+        const CHUNK: usize = 2048;
         let bind_data = func.get_bind_data();
         let init_data = func.get_init_data();
-        let rows = bind_data.rows.lock().unwrap();
+        let rows = &bind_data.rows;
 
-        let offset = init_data.offset.load(Ordering::Relaxed);
-        if offset >= rows.len() {
+        // Claim a disjoint range lock-free; the slice is immutable after bind,
+        // so workers read it concurrently without synchronization.
+        let start = init_data.next.fetch_add(CHUNK, Ordering::Relaxed);
+        if start >= rows.len() {
             output.set_len(0);
             return Ok(());
         }
+        let end = std::cmp::min(start + CHUNK, rows.len());
 
-        let batch_size = std::cmp::min(2048, rows.len() - offset);
-        for i in 0..batch_size {
-            T::write_row(output, i, &rows[offset + i]);
+        for (i, row) in rows[start..end].iter().enumerate() {
+            T::write_row(output, i, row);
         }
-
-        output.set_len(batch_size);
-        init_data.offset.store(offset + batch_size, Ordering::Relaxed);
+        output.set_len(end - start);
         Ok(())
     }
 
     fn named_parameters() -> Option<Vec<(String, LogicalTypeHandle)>> {
-        Some(vec![("path".to_string(), LogicalTypeHandle::from(LogicalTypeId::Varchar))])
+        Some(vec![
+            ("path".to_string(), LogicalTypeHandle::from(LogicalTypeId::Varchar)),
+            ("source".to_string(), LogicalTypeHandle::from(LogicalTypeId::Varchar)),
+        ])
     }
 }